@@ -1,3 +1,4 @@
+#![no_std]
 #![deny(missing_docs)]
 
 //! Write-only references.
@@ -16,8 +17,95 @@
 //! Most functions should not take a `WriteRef` or `WriteSlice` directly;
 //! instead, they should take an `impl Into<WriteRef<'a, T>>` so that callers
 //! can pass in a `&mut T`.
+//!
+//! This crate is `no_std`. The `std` feature, which is enabled by default,
+//! lets `MustWrite` avoid panicking while the thread is already panicking and
+//! adds `log_stderr`. The `alloc` feature, which is implied by `std`, enables sinks
+//! that need to allocate. The `testing` feature adds sinks that record their
+//! writes for use in tests, and implies `alloc`. The `derive` feature enables
+//! `#[derive(WriteRefFields)]`, and the `zeroize` feature adds sinks that
+//! securely clear previous contents using the `zeroize` crate.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(feature = "derive")]
+extern crate write_ref_derive;
+#[cfg(feature = "zeroize")]
+extern crate zeroize;
+
+/// Derive write-only references to each field of a struct.
+///
+/// This generates a `<Struct>Writers` type with a method for each field that
+/// returns a `WriteRef` to it, and a `writers` method on the struct that
+/// creates one. Each method has the same visibility as its field, so code
+/// that can't access a field can't write to it either. Only structs with
+/// named fields and no generic parameters are supported. It is only
+/// available with the `derive` feature.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate write_ref;
+///
+/// #[derive(WriteRefFields, Default)]
+/// struct Output {
+///     count: u32,
+///     name: String,
+/// }
+///
+/// fn produce(mut out: OutputWriters) {
+///     out.count().write(3);
+///     out.name().write(String::from("three"));
+/// }
+///
+/// fn main() {
+///     let mut out = Output::default();
+///     produce(out.writers());
+///     assert_eq!(out.count, 3);
+///     assert_eq!(out.name, "three");
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate write_ref;
+///
+/// mod account {
+///     #[derive(WriteRefFields, Default)]
+///     pub struct Account {
+///         pub owner: String,
+///         balance: u64,
+///     }
+/// }
+///
+/// fn main() {
+///     let mut account = account::Account::default();
+///     account.writers().owner().write(String::from("me"));
+///     account.writers().balance().write(5); // error: `balance` is private
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use write_ref_derive::WriteRefFields;
 
-use std::convert::From;
+use core::cell::{Cell, RefCell};
+use core::convert::{From, Infallible, TryFrom};
+use core::error::Error;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::ops::Range;
+use core::pin::Pin;
+use core::sync::atomic::{self, Ordering};
+
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 /// Represents a write-only reference.
 ///
@@ -45,6 +133,251 @@ impl<'a, T: 'a> WriteRef<'a, T> {
     pub fn write(&mut self, val: T) {
         *self.0 = val;
     }
+
+    /// Adapt this reference to accept values of another type, converting
+    /// them with `f` before they are written.
+    ///
+    /// The returned `MapWrite` stores `f` by value, so it can't outlive
+    /// anything `f` borrows in addition to the original reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteRef;
+    /// # fn main() {
+    /// let mut fahrenheit = 0.0;
+    /// {
+    ///     let mut celsius = WriteRef::from(&mut fahrenheit).contramap(|c: f64| c * 1.8 + 32.0);
+    ///     celsius.write(100.0);
+    /// }
+    /// assert_eq!(fahrenheit, 212.0);
+    /// # }
+    /// ```
+    pub fn contramap<U, F: FnMut(U) -> T>(self, f: F) -> MapWrite<'a, T, F> {
+        MapWrite { inner: self, f }
+    }
+
+    /// Write the default value of `T` to this reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteRef;
+    /// #[derive(Debug, PartialEq)]
+    /// struct Level(u8);
+    /// impl Default for Level {
+    ///     fn default() -> Self {
+    ///         Level(3)
+    ///     }
+    /// }
+    /// fn main() {
+    ///     let mut a = 5;
+    ///     let mut b = String::from("text");
+    ///     let mut c = Level(9);
+    ///     WriteRef::from(&mut a).write_default();
+    ///     WriteRef::from(&mut b).write_default();
+    ///     WriteRef::from(&mut c).write_default();
+    ///     assert_eq!(a, 0);
+    ///     assert_eq!(b, "");
+    ///     assert_eq!(c, Level(3));
+    /// }
+    /// ```
+    pub fn write_default(&mut self)
+    where
+        T: Default,
+    {
+        self.write(T::default());
+    }
+
+    /// Write the value returned by `f` to this reference.
+    ///
+    /// `f` is called exactly once. Guard sinks such as `WriteOnce` also
+    /// provide this method, and they check whether the write is allowed
+    /// before calling `f`, so an expensive value is never computed just to be
+    /// rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteRef;
+    /// # fn main() {
+    /// let mut a = 0;
+    /// let mut calls = 0;
+    /// WriteRef::from(&mut a).write_with(|| {
+    ///     calls += 1;
+    ///     42
+    /// });
+    /// assert_eq!((a, calls), (42, 1));
+    /// # }
+    /// ```
+    pub fn write_with<F: FnOnce() -> T>(&mut self, f: F) {
+        self.write(f());
+    }
+
+    /// Write a value to this reference if it satisfies `pred`.
+    ///
+    /// Returns whether the value was written. If it wasn't, the value is
+    /// dropped and the reference keeps its previous contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteRef;
+    /// # fn main() {
+    /// let mut a = 0;
+    /// {
+    ///     let mut a_ref = WriteRef::from(&mut a);
+    ///     assert!(a_ref.write_if(4, |v| v % 2 == 0));
+    ///     assert!(!a_ref.write_if(5, |v| v % 2 == 0));
+    /// }
+    /// assert_eq!(a, 4);
+    /// # }
+    /// ```
+    pub fn write_if<F: FnOnce(&T) -> bool>(&mut self, val: T, pred: F) -> bool {
+        let write = pred(&val);
+        if write {
+            self.write(val);
+        }
+        write
+    }
+
+    /// Adapt this reference to accept any type that `T` can be converted
+    /// from with `From`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteRef;
+    /// struct Meters(f64);
+    /// impl From<f64> for Meters {
+    ///     fn from(m: f64) -> Self {
+    ///         Meters(m)
+    ///     }
+    /// }
+    /// fn main() {
+    ///     let mut a = 0u32;
+    ///     WriteRef::from(&mut a).cast().write(7u8);
+    ///     assert_eq!(a, 7);
+    ///
+    ///     let mut b = Meters(0.0);
+    ///     WriteRef::from(&mut b).cast().write(1.5);
+    ///     assert_eq!(b.0, 1.5);
+    /// }
+    /// ```
+    pub fn cast<U>(self) -> MapWrite<'a, T, fn(U) -> T>
+    where
+        T: From<U>,
+    {
+        self.contramap(T::from)
+    }
+
+    /// Adapt this reference to call `f` with each value before it is written.
+    ///
+    /// `f` only receives a shared reference, so it can't change or prevent
+    /// the write.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteRef;
+    /// # fn main() {
+    /// let mut a = 0;
+    /// let mut seen = Vec::new();
+    /// {
+    ///     let mut a_ref = WriteRef::from(&mut a).inspect(|v: &i32| seen.push(*v));
+    ///     a_ref.write(1);
+    ///     a_ref.write(2);
+    /// }
+    /// assert_eq!(seen, [1, 2]);
+    /// assert_eq!(a, 2);
+    /// # }
+    /// ```
+    pub fn inspect<F: FnMut(&T)>(self, f: F) -> Inspect<'a, T, F> {
+        Inspect { inner: self, f }
+    }
+
+    /// Adapt this reference to accept another integer type, saturating
+    /// values that don't fit in `T` to its minimum or maximum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteRef;
+    /// # fn main() {
+    /// let mut a = 0u8;
+    /// WriteRef::from(&mut a).saturating_cast().write(300i64);
+    /// assert_eq!(a, 255);
+    /// WriteRef::from(&mut a).saturating_cast().write(-5i64);
+    /// assert_eq!(a, 0);
+    /// WriteRef::from(&mut a).saturating_cast().write(7i64);
+    /// assert_eq!(a, 7);
+    /// # }
+    /// ```
+    pub fn saturating_cast<U>(self) -> MapWrite<'a, T, fn(U) -> T>
+    where
+        T: SaturatingFrom<U>,
+    {
+        self.contramap(T::saturating_from)
+    }
+
+    /// Write a value to this reference if it differs from the current value.
+    ///
+    /// Returns whether the value was written. This compares against the
+    /// current value, so unlike most methods here it reads from the
+    /// reference. It's only available on `WriteRef` itself, since other
+    /// sinks can't read what they hold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteRef;
+    /// # fn main() {
+    /// let mut a = 3;
+    /// {
+    ///     let mut a_ref = WriteRef::from(&mut a);
+    ///     assert!(!a_ref.write_if_changed(3));
+    ///     assert!(a_ref.write_if_changed(4));
+    /// }
+    /// assert_eq!(a, 4);
+    /// # }
+    /// ```
+    pub fn write_if_changed(&mut self, val: T) -> bool
+    where
+        T: PartialEq,
+    {
+        let changed = *self.0 != val;
+        if changed {
+            self.write(val);
+        }
+        changed
+    }
+}
+
+impl<'a, T: 'a> WriteRef<'a, Option<T>> {
+    /// Adapt this reference to accept a `T`, writing it as `Some`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteRef;
+    /// # fn main() {
+    /// let mut a = None;
+    /// WriteRef::from(&mut a).wrap_some().write(3);
+    /// assert_eq!(a, Some(3));
+    /// # }
+    /// ```
+    pub fn wrap_some(self) -> SomeWrite<'a, T> {
+        SomeWrite(self.0)
+    }
 }
 
 impl<'a, T: 'a> From<&'a mut T> for WriteRef<'a, T> {
@@ -53,6 +386,47 @@ impl<'a, T: 'a> From<&'a mut T> for WriteRef<'a, T> {
     }
 }
 
+/// Allows writing through a pinned reference.
+///
+/// This is only available for `Unpin` types, since writing a new value moves
+/// out the old one. Pinned `!Unpin` values can't be written to this way.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use std::pin::Pin;
+/// # use write_ref::WriteRef;
+/// # fn main() {
+/// let mut a = 0u32;
+/// WriteRef::from(Pin::new(&mut a)).write(5);
+/// assert_eq!(a, 5);
+/// # }
+/// ```
+impl<'a, T: Unpin + 'a> From<Pin<&'a mut T>> for WriteRef<'a, T> {
+    fn from(inner: Pin<&'a mut T>) -> Self {
+        WriteRef(Pin::get_mut(inner))
+    }
+}
+
+/// A write-only reference that converts values before writing them.
+///
+/// This is returned by [`WriteRef::contramap`](struct.WriteRef.html#method.contramap).
+pub struct MapWrite<'a, T: 'a, F> {
+    inner: WriteRef<'a, T>,
+    f: F,
+}
+
+impl<'a, T: 'a, F> MapWrite<'a, T, F> {
+    /// Convert a value and write it to the underlying reference.
+    pub fn write<U>(&mut self, val: U)
+    where
+        F: FnMut(U) -> T,
+    {
+        self.inner.write((self.f)(val));
+    }
+}
+
 /// Represents a write-only buffer.
 ///
 /// You only write to individual elements of this slice; you can't modify the
@@ -91,10 +465,5506 @@ impl<'a, T: 'a> WriteSlice<'a, T> {
     pub fn write(&mut self, idx: usize, val: T) {
         self.0[idx] = val;
     }
-}
 
-impl<'a, T: 'a> From<&'a mut [T]> for WriteSlice<'a, T> {
-    fn from(inner: &'a mut [T]) -> Self {
-        WriteSlice(inner)
+    /// Write a value to an element of this slice, or return it if `idx` is
+    /// out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0, 0];
+    /// {
+    ///     let mut slice = WriteSlice::from(&mut buf as &mut [_]);
+    ///     assert_eq!(slice.try_write(1, 5), Ok(()));
+    ///     assert_eq!(slice.try_write(2, 6), Err(6));
+    /// }
+    /// assert_eq!(buf, [0, 5]);
+    /// # }
+    /// ```
+    pub fn try_write(&mut self, idx: usize, val: T) -> Result<(), T> {
+        match self.0.get_mut(idx) {
+            Some(elem) => {
+                *elem = val;
+                Ok(())
+            }
+            None => Err(val),
+        }
+    }
+
+    /// Returns the number of elements in this slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// fn fill_ones(output: &mut WriteSlice<u8>) {
+    ///     for i in 0..output.len() {
+    ///         output.write(i, 1);
+    ///     }
+    /// }
+    /// fn main() {
+    ///     let mut buf = [0u8; 3];
+    ///     fill_ones(&mut WriteSlice::from(&mut buf as &mut [_]));
+    ///     assert_eq!(buf, [1, 1, 1]);
+    ///
+    ///     let mut empty: [u8; 0] = [];
+    ///     let slice = WriteSlice::from(&mut empty as &mut [_]);
+    ///     assert_eq!(slice.len(), 0);
+    ///     assert!(slice.is_empty());
+    /// }
+    /// ```
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if this slice has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Write a value to every element of this slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [1, 2, 3];
+    /// WriteSlice::from(&mut buf as &mut [_]).fill(0);
+    /// assert_eq!(buf, [0, 0, 0]);
+    ///
+    /// let mut empty: [i32; 0] = [];
+    /// WriteSlice::from(&mut empty as &mut [_]).fill(0);
+    /// assert_eq!(empty, []);
+    /// # }
+    /// ```
+    pub fn fill(&mut self, val: T)
+    where
+        T: Copy,
+    {
+        self.0.fill(val);
+    }
+
+    /// Write values returned by calling `f` to every element of this slice.
+    ///
+    /// `f` is called once per element, in order from the first element to the
+    /// last.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 4];
+    /// let mut next = 0;
+    /// WriteSlice::from(&mut buf as &mut [_]).fill_with(|| {
+    ///     next += 10;
+    ///     next
+    /// });
+    /// assert_eq!(buf, [10, 20, 30, 40]);
+    /// assert_eq!(next, 40);
+    /// # }
+    /// ```
+    pub fn fill_with<F: FnMut() -> T>(&mut self, mut f: F) {
+        for elem in self.0.iter_mut() {
+            *elem = f();
+        }
+    }
+
+    /// Copy all elements from `src` into this slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` has a different length than this slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 3];
+    /// WriteSlice::from(&mut buf as &mut [_]).copy_from_slice(&[1, 2, 3]);
+    /// assert_eq!(buf, [1, 2, 3]);
+    /// # }
+    /// ```
+    pub fn copy_from_slice(&mut self, src: &[T])
+    where
+        T: Copy,
+    {
+        self.0.copy_from_slice(src);
+    }
+
+    /// Copy all elements from `src` into this slice, or return an error
+    /// without writing anything if the lengths differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::{LengthMismatch, WriteSlice};
+    /// # fn main() {
+    /// let mut buf = [0; 3];
+    /// {
+    ///     let mut slice = WriteSlice::from(&mut buf as &mut [_]);
+    ///     assert_eq!(slice.try_copy_from_slice(&[1, 2]), Err(LengthMismatch));
+    ///     assert_eq!(slice.try_copy_from_slice(&[1, 2, 3]), Ok(()));
+    /// }
+    /// assert_eq!(buf, [1, 2, 3]);
+    /// # }
+    /// ```
+    pub fn try_copy_from_slice(&mut self, src: &[T]) -> Result<(), LengthMismatch>
+    where
+        T: Copy,
+    {
+        if src.len() != self.0.len() {
+            return Err(LengthMismatch);
+        }
+        self.0.copy_from_slice(src);
+        Ok(())
+    }
+
+    /// Clone all elements from `src` into this slice, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` has a different length than this slice. The lengths
+    /// are checked before anything is written, so no elements are modified in
+    /// this case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let src = [String::from("a"), String::from("b")];
+    /// let mut buf = [String::new(), String::new()];
+    /// WriteSlice::from(&mut buf as &mut [_]).clone_from_slice(&src);
+    /// assert_eq!(buf, src);
+    /// assert_eq!(src, ["a", "b"]);
+    /// # }
+    /// ```
+    ///
+    /// ```should_panic
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [String::new(), String::new()];
+    /// WriteSlice::from(&mut buf as &mut [_]).clone_from_slice(&[String::from("a")]);
+    /// # }
+    /// ```
+    pub fn clone_from_slice(&mut self, src: &[T])
+    where
+        T: Clone,
+    {
+        self.0.clone_from_slice(src);
+    }
+
+    /// Write the items of an iterator to this slice, starting at index 0.
+    ///
+    /// Writing stops once either the iterator or the slice is exhausted. Any
+    /// items beyond the end of the slice are not consumed. Returns the number
+    /// of elements that were written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 3];
+    /// {
+    ///     let mut slice = WriteSlice::from(&mut buf as &mut [_]);
+    ///     assert_eq!(slice.write_iter(vec![1, 2]), 2);
+    ///     assert_eq!(slice.write_iter(vec![1, 2, 3]), 3);
+    ///     assert_eq!(slice.write_iter(vec![4, 5, 6, 7]), 3);
+    /// }
+    /// assert_eq!(buf, [4, 5, 6]);
+    /// # }
+    /// ```
+    pub fn write_iter<I: IntoIterator<Item = T>>(&mut self, iter: I) -> usize {
+        let mut written = 0;
+        for (elem, val) in self.0.iter_mut().zip(iter) {
+            *elem = val;
+            written += 1;
+        }
+        written
+    }
+
+    /// Returns a write-only reference to an element of this slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::{WriteRef, WriteSlice};
+    /// fn clear<'a>(output: impl Into<WriteRef<'a, i32>>) {
+    ///     output.into().write(0);
+    /// }
+    /// fn main() {
+    ///     let mut buf = [1, 2, 3];
+    ///     {
+    ///         let mut slice = WriteSlice::from(&mut buf as &mut [_]);
+    ///         clear(slice.write_ref_at(1));
+    ///         assert!(slice.try_write_ref_at(3).is_none());
+    ///     }
+    ///     assert_eq!(buf, [1, 0, 3]);
+    /// }
+    /// ```
+    ///
+    /// Only one element reference can exist at a time:
+    ///
+    /// ```compile_fail
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [1, 2, 3];
+    /// let mut slice = WriteSlice::from(&mut buf as &mut [_]);
+    /// let mut a = slice.write_ref_at(0);
+    /// let mut b = slice.write_ref_at(1);
+    /// a.write(0);
+    /// b.write(0);
+    /// # }
+    /// ```
+    pub fn write_ref_at(&mut self, idx: usize) -> WriteRef<'_, T> {
+        WriteRef(&mut self.0[idx])
+    }
+
+    /// Returns a write-only reference to an element of this slice, or `None`
+    /// if `idx` is out of bounds.
+    pub fn try_write_ref_at(&mut self, idx: usize) -> Option<WriteRef<'_, T>> {
+        self.0.get_mut(idx).map(WriteRef)
+    }
+
+    /// Divide this slice into two at an index.
+    ///
+    /// The first slice contains the elements before `mid`, and the second
+    /// contains the rest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 5];
+    /// {
+    ///     let (mut left, mut right) = WriteSlice::from(&mut buf as &mut [_]).split_at_write(2);
+    ///     assert_eq!((left.len(), right.len()), (2, 3));
+    ///     left.fill(1);
+    ///     right.fill(2);
+    /// }
+    /// assert_eq!(buf, [1, 1, 2, 2, 2]);
+    ///
+    /// let (left, right) = WriteSlice::from(&mut buf as &mut [_]).split_at_write(0);
+    /// assert_eq!((left.len(), right.len()), (0, 5));
+    /// let (left, right) = right.split_at_write(5);
+    /// assert_eq!((left.len(), right.len()), (5, 0));
+    /// # }
+    /// ```
+    ///
+    /// ```should_panic
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 5];
+    /// WriteSlice::from(&mut buf as &mut [_]).split_at_write(6);
+    /// # }
+    /// ```
+    pub fn split_at_write(self, mid: usize) -> (WriteSlice<'a, T>, WriteSlice<'a, T>) {
+        let (left, right) = self.0.split_at_mut(mid);
+        (WriteSlice(left), WriteSlice(right))
+    }
+
+    /// Returns an iterator over write-only references to each element of this
+    /// slice, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0usize; 3];
+    /// for (i, mut elem) in WriteSlice::from(&mut buf as &mut [_]).write_refs().enumerate() {
+    ///     elem.write(i * 10);
+    /// }
+    /// assert_eq!(buf, [0, 10, 20]);
+    /// # }
+    /// ```
+    pub fn write_refs(self) -> impl Iterator<Item = WriteRef<'a, T>> {
+        self.0.iter_mut().map(WriteRef)
+    }
+
+    /// Returns an iterator over the indices of this slice paired with
+    /// write-only references to the corresponding elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0usize; 3];
+    /// let mut visited = 0;
+    /// for (i, mut elem) in WriteSlice::from(&mut buf as &mut [_]).enumerate_writes() {
+    ///     elem.write(i + 1);
+    ///     visited += 1;
+    /// }
+    /// assert_eq!(buf, [1, 2, 3]);
+    /// assert_eq!(visited, 3);
+    /// # }
+    /// ```
+    pub fn enumerate_writes(self) -> impl Iterator<Item = (usize, WriteRef<'a, T>)> {
+        self.write_refs().enumerate()
+    }
+
+    /// Returns a write-only view of a range of elements of this slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 4];
+    /// WriteSlice::from(&mut buf as &mut [_]).subslice(1..3).fill(1);
+    /// assert_eq!(buf, [0, 1, 1, 0]);
+    ///
+    /// assert!(WriteSlice::from(&mut buf as &mut [_]).subslice(2..2).is_empty());
+    /// WriteSlice::from(&mut buf as &mut [_]).subslice(0..4).fill(2);
+    /// assert_eq!(buf, [2, 2, 2, 2]);
+    /// # }
+    /// ```
+    ///
+    /// ```should_panic
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 4];
+    /// WriteSlice::from(&mut buf as &mut [_]).subslice(2..5);
+    /// # }
+    /// ```
+    pub fn subslice(self, range: Range<usize>) -> WriteSlice<'a, T> {
+        WriteSlice(&mut self.0[range])
+    }
+
+    /// Returns a write-only view of this slice with its elements in reverse
+    /// order, so that index 0 refers to the last element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 3];
+    /// {
+    ///     let mut rev = WriteSlice::from(&mut buf).reversed();
+    ///     for i in 0..rev.len() {
+    ///         rev.write(i, i + 1);
+    ///     }
+    /// }
+    /// assert_eq!(buf, [3, 2, 1]);
+    /// # }
+    /// ```
+    ///
+    /// ```should_panic
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 3];
+    /// WriteSlice::from(&mut buf).reversed().write(3, 1);
+    /// # }
+    /// ```
+    pub fn reversed(self) -> Reversed<'a, T> {
+        Reversed(self.0)
+    }
+
+    /// Returns a write-only view of every `step`th element of this slice,
+    /// starting with the first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 6];
+    /// {
+    ///     let mut even = WriteSlice::from(&mut buf).stride(2);
+    ///     assert_eq!(even.len(), 3);
+    ///     for i in 0..even.len() {
+    ///         even.write(i, 1);
+    ///     }
+    /// }
+    /// assert_eq!(buf, [1, 0, 1, 0, 1, 0]);
+    /// assert_eq!(WriteSlice::from(&mut buf).stride(4).len(), 2);
+    /// # }
+    /// ```
+    pub fn stride(self, step: usize) -> Stride<'a, T> {
+        assert!(step != 0, "stride step must be non-zero");
+        Stride {
+            slice: self.0,
+            step,
+        }
+    }
+
+    /// Returns an iterator over non-overlapping chunks of this slice, each of
+    /// which has `size` elements except possibly the last.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 5];
+    /// let mut lens = Vec::new();
+    /// for (i, mut chunk) in WriteSlice::from(&mut buf).write_chunks(2).enumerate() {
+    ///     lens.push(chunk.len());
+    ///     chunk.fill(i);
+    /// }
+    /// assert_eq!(lens, [2, 2, 1]);
+    /// assert_eq!(buf, [0, 0, 1, 1, 2]);
+    /// # }
+    /// ```
+    pub fn write_chunks(self, size: usize) -> impl Iterator<Item = WriteSlice<'a, T>> {
+        self.0.chunks_mut(size).map(WriteSlice)
+    }
+
+    /// Write a clone of a value to each of the given indices, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index is out of bounds. The indices before it will
+    /// already have been written to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 5];
+    /// WriteSlice::from(&mut buf).scatter(&[0, 3, 3], 7);
+    /// assert_eq!(buf, [7, 0, 0, 7, 0]);
+    /// # }
+    /// ```
+    ///
+    /// ```should_panic
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 5];
+    /// WriteSlice::from(&mut buf).scatter(&[1, 5], 7);
+    /// # }
+    /// ```
+    pub fn scatter(&mut self, indices: &[usize], val: T)
+    where
+        T: Clone,
+    {
+        for &idx in indices {
+            self.0[idx] = val.clone();
+        }
+    }
+
+    /// Returns a write-only view of this slice rotated by `offset`, so that
+    /// index `i` refers to the element at `(i + offset) % len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 4];
+    /// {
+    ///     let mut rotated = WriteSlice::from(&mut buf).rotated(1);
+    ///     for i in 0..rotated.len() {
+    ///         rotated.write(i, i);
+    ///     }
+    /// }
+    /// assert_eq!(buf, [3, 0, 1, 2]);
+    ///
+    /// for &offset in &[0, 4] {
+    ///     let mut rotated = WriteSlice::from(&mut buf).rotated(offset);
+    ///     for i in 0..rotated.len() {
+    ///         rotated.write(i, i);
+    ///     }
+    ///     assert_eq!(buf, [0, 1, 2, 3]);
+    /// }
+    /// # }
+    /// ```
+    pub fn rotated(self, offset: usize) -> Rotated<'a, T> {
+        let offset = if self.0.is_empty() {
+            0
+        } else {
+            offset % self.0.len()
+        };
+        Rotated {
+            slice: self.0,
+            offset,
+        }
+    }
+
+    /// Returns a write-only reference to the first element of this slice and
+    /// a write-only view of the rest, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// fn countdown(output: WriteSlice<u32>) {
+    ///     if let Some((mut first, rest)) = output.split_first_write() {
+    ///         first.write(rest.len() as u32);
+    ///         countdown(rest);
+    ///     }
+    /// }
+    /// fn main() {
+    ///     let mut buf = [0; 3];
+    ///     countdown(WriteSlice::from(&mut buf));
+    ///     assert_eq!(buf, [2, 1, 0]);
+    ///
+    ///     let mut one = [0];
+    ///     let (_, rest) = WriteSlice::from(&mut one).split_first_write().unwrap();
+    ///     assert!(rest.is_empty());
+    ///
+    ///     let mut empty: [u32; 0] = [];
+    ///     assert!(WriteSlice::from(&mut empty).split_first_write().is_none());
+    /// }
+    /// ```
+    pub fn split_first_write(self) -> Option<(WriteRef<'a, T>, WriteSlice<'a, T>)> {
+        self.0
+            .split_first_mut()
+            .map(|(first, rest)| (WriteRef(first), WriteSlice(rest)))
+    }
+
+    /// Returns a write-only reference to the last element of this slice and
+    /// a write-only view of the rest, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 3];
+    /// {
+    ///     let (mut last, mut rest) = WriteSlice::from(&mut buf).split_last_write().unwrap();
+    ///     last.write(9);
+    ///     rest.fill(1);
+    /// }
+    /// assert_eq!(buf, [1, 1, 9]);
+    /// # }
+    /// ```
+    pub fn split_last_write(self) -> Option<(WriteRef<'a, T>, WriteSlice<'a, T>)> {
+        self.0
+            .split_last_mut()
+            .map(|(last, rest)| (WriteRef(last), WriteSlice(rest)))
+    }
+
+    /// Adapt this slice to call `f` with each index and value before the
+    /// value is written.
+    ///
+    /// `f` only receives a shared reference to the value, so it can't change
+    /// or prevent the write.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 2];
+    /// let mut seen = Vec::new();
+    /// {
+    ///     let mut slice = WriteSlice::from(&mut buf).inspect_elem(|i, v: &i32| seen.push((i, *v)));
+    ///     slice.write(1, 5);
+    ///     slice.write(0, 6);
+    /// }
+    /// assert_eq!(seen, [(1, 5), (0, 6)]);
+    /// assert_eq!(buf, [6, 5]);
+    /// # }
+    /// ```
+    pub fn inspect_elem<F: FnMut(usize, &T)>(self, f: F) -> InspectSlice<'a, T, F> {
+        InspectSlice { inner: self, f }
+    }
+
+    /// Swap two elements of this slice.
+    ///
+    /// This moves existing contents around without exposing them, so it
+    /// keeps the slice write-only. If `a == b`, nothing changes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [1, 2, 3];
+    /// {
+    ///     let mut slice = WriteSlice::from(&mut buf);
+    ///     slice.swap(0, 2);
+    ///     slice.swap(1, 1);
+    /// }
+    /// assert_eq!(buf, [3, 2, 1]);
+    /// # }
+    /// ```
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.0.swap(a, b);
+    }
+
+    /// Write a value to an element of this slice if `idx` is in bounds.
+    ///
+    /// Returns whether the value was written. If it wasn't, the value is
+    /// dropped. Use `try_write` to get the value back instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 2];
+    /// {
+    ///     let mut slice = WriteSlice::from(&mut buf);
+    ///     assert!(slice.write_checked(1, 5));
+    ///     assert!(!slice.write_checked(2, 6));
+    /// }
+    /// assert_eq!(buf, [0, 5]);
+    /// # }
+    /// ```
+    pub fn write_checked(&mut self, idx: usize, val: T) -> bool {
+        self.try_write(idx, val).is_ok()
+    }
+
+    /// Write the items of an iterator to this slice, requiring that it
+    /// yields exactly as many items as the slice has elements.
+    ///
+    /// If the iterator is too short, the elements after its last item are
+    /// left unwritten. If it is too long, every element is written and one
+    /// extra item is consumed and dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::{LengthError, WriteSlice};
+    /// # fn main() {
+    /// let mut buf = [0; 3];
+    /// {
+    ///     let mut slice = WriteSlice::from(&mut buf);
+    ///     assert_eq!(slice.write_exact(vec![1, 2, 3]), Ok(()));
+    ///     assert_eq!(slice.write_exact(vec![4, 5]), Err(LengthError::TooShort));
+    /// }
+    /// assert_eq!(buf, [4, 5, 3]);
+    /// let result = WriteSlice::from(&mut buf).write_exact(6..10);
+    /// assert_eq!(result, Err(LengthError::TooLong));
+    /// assert_eq!(buf, [6, 7, 8]);
+    /// # }
+    /// ```
+    pub fn write_exact<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), LengthError> {
+        let mut iter = iter.into_iter();
+        if self.write_iter(iter.by_ref()) < self.0.len() {
+            Err(LengthError::TooShort)
+        } else if iter.next().is_some() {
+            Err(LengthError::TooLong)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns write-only references to several distinct elements of this
+    /// slice at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index is out of bounds or if any two indices are equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 5];
+    /// {
+    ///     let mut slice = WriteSlice::from(&mut buf);
+    ///     let [mut a, mut b, mut c] = slice.write_refs_disjoint([4, 0, 2]);
+    ///     a.write(1);
+    ///     b.write(2);
+    ///     c.write(3);
+    /// }
+    /// assert_eq!(buf, [2, 0, 3, 0, 1]);
+    /// # }
+    /// ```
+    ///
+    /// ```should_panic
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 5];
+    /// WriteSlice::from(&mut buf).write_refs_disjoint([1, 1]);
+    /// # }
+    /// ```
+    ///
+    /// ```should_panic
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 5];
+    /// WriteSlice::from(&mut buf).write_refs_disjoint([1, 5]);
+    /// # }
+    /// ```
+    pub fn write_refs_disjoint<const N: usize>(
+        &mut self,
+        indices: [usize; N],
+    ) -> [WriteRef<'_, T>; N] {
+        let len = self.0.len();
+        for (i, &idx) in indices.iter().enumerate() {
+            check_index(idx, len);
+            assert!(
+                !indices[..i].contains(&idx),
+                "index {} was given more than once",
+                idx
+            );
+        }
+        let ptr = self.0.as_mut_ptr();
+        // SAFETY: every index is in bounds and no two are equal, so the
+        // references don't overlap.
+        indices.map(|idx| WriteRef(unsafe { &mut *ptr.add(idx) }))
+    }
+
+    /// Returns a write-only view of the first `n` elements of this slice, or
+    /// of the whole slice if it has fewer than `n` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 4];
+    /// {
+    ///     let mut first = WriteSlice::from(&mut buf).take(2);
+    ///     assert_eq!(first.len(), 2);
+    ///     first.fill(1);
+    ///     assert!(first.try_write(2, 1).is_err());
+    /// }
+    /// assert_eq!(buf, [1, 1, 0, 0]);
+    /// assert_eq!(WriteSlice::from(&mut buf).take(4).len(), 4);
+    /// assert_eq!(WriteSlice::from(&mut buf).take(10).len(), 4);
+    /// # }
+    /// ```
+    pub fn take(self, n: usize) -> WriteSlice<'a, T> {
+        let len = self.0.len();
+        WriteSlice(&mut self.0[..n.min(len)])
+    }
+
+    /// Write the default value of `T` to every element of this slice.
+    ///
+    /// `T::default` is called once per element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use std::cell::Cell;
+    /// # use write_ref::WriteSlice;
+    /// thread_local!(static CALLS: Cell<usize> = Cell::new(0));
+    /// #[derive(Debug, PartialEq)]
+    /// struct Counted(u8);
+    /// impl Default for Counted {
+    ///     fn default() -> Self {
+    ///         CALLS.with(|c| c.set(c.get() + 1));
+    ///         Counted(0)
+    ///     }
+    /// }
+    /// fn main() {
+    ///     let mut buf = [Counted(1), Counted(2), Counted(3)];
+    ///     WriteSlice::from(&mut buf).fill_default();
+    ///     assert_eq!(buf, [Counted(0), Counted(0), Counted(0)]);
+    ///     assert_eq!(CALLS.with(Cell::get), 3);
+    /// }
+    /// ```
+    pub fn fill_default(&mut self)
+    where
+        T: Default,
+    {
+        self.fill_with(T::default);
     }
+
+    /// Write values returned by calling `f` with each index to the elements
+    /// of this slice, in order, stopping at the first error.
+    ///
+    /// If `f` returns an error, the elements before the failing index have
+    /// already been written, and the rest are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 3];
+    /// let input = ["1", "2", "x"];
+    /// let result = WriteSlice::from(&mut buf).try_fill_with(|i| input[i].parse());
+    /// assert!(result.is_err());
+    /// assert_eq!(buf, [1, 2, 0]);
+    ///
+    /// let result: Result<(), ()> = WriteSlice::from(&mut buf).try_fill_with(|i| Ok(i * 10));
+    /// assert_eq!(result, Ok(()));
+    /// assert_eq!(buf, [0, 10, 20]);
+    /// # }
+    /// ```
+    pub fn try_fill_with<E, F: FnMut(usize) -> Result<T, E>>(&mut self, mut f: F) -> Result<(), E> {
+        for (i, elem) in self.0.iter_mut().enumerate() {
+            *elem = f(i)?;
+        }
+        Ok(())
+    }
+
+    /// Write a value to every element in a range of this slice.
+    ///
+    /// The value is cloned for every element but the last, so an empty range
+    /// doesn't clone it at all.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [1; 5];
+    /// {
+    ///     let mut slice = WriteSlice::from(&mut buf);
+    ///     slice.fill_range(1..4, 0);
+    ///     slice.fill_range(2..2, 9);
+    /// }
+    /// assert_eq!(buf, [1, 0, 0, 0, 1]);
+    /// WriteSlice::from(&mut buf).fill_range(0..5, 2);
+    /// assert_eq!(buf, [2; 5]);
+    /// # }
+    /// ```
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use std::cell::Cell;
+    /// # use write_ref::WriteSlice;
+    /// struct Counted<'c>(&'c Cell<usize>);
+    /// impl<'c> Clone for Counted<'c> {
+    ///     fn clone(&self) -> Self {
+    ///         self.0.set(self.0.get() + 1);
+    ///         Counted(self.0)
+    ///     }
+    /// }
+    /// fn main() {
+    ///     let clones = Cell::new(0);
+    ///     let mut buf = [Counted(&clones), Counted(&clones), Counted(&clones)];
+    ///     let mut slice = WriteSlice::from(&mut buf);
+    ///     slice.fill_range(1..1, Counted(&clones));
+    ///     assert_eq!(clones.get(), 0);
+    ///     slice.fill_range(0..3, Counted(&clones));
+    ///     assert_eq!(clones.get(), 2);
+    /// }
+    /// ```
+    ///
+    /// ```should_panic
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [1; 5];
+    /// WriteSlice::from(&mut buf).fill_range(3..6, 0);
+    /// # }
+    /// ```
+    pub fn fill_range(&mut self, range: Range<usize>, val: T)
+    where
+        T: Clone,
+    {
+        if let Some((last, rest)) = self.0[range].split_last_mut() {
+            for elem in rest {
+                *elem = val.clone();
+            }
+            *last = val;
+        }
+    }
+
+    /// Returns a write-only view of this slice with `len` elements, where
+    /// index `i` refers to the element at `f(i)`.
+    ///
+    /// `f` must return indices that are in bounds for this slice, or writing
+    /// panics. If `f` maps two indices to the same element, the later write
+    /// replaces the earlier one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let table = [2, 0, 3, 1];
+    /// let mut buf = [0; 4];
+    /// {
+    ///     let mut permuted = WriteSlice::from(&mut buf).map_index(|i| table[i], 4);
+    ///     for i in 0..permuted.len() {
+    ///         permuted.write(i, i * 10);
+    ///     }
+    /// }
+    /// assert_eq!(buf, [10, 30, 0, 20]);
+    /// # }
+    /// ```
+    pub fn map_index<F: Fn(usize) -> usize>(self, f: F, len: usize) -> MapIndex<'a, T, F> {
+        MapIndex {
+            slice: self.0,
+            f,
+            len,
+        }
+    }
+
+    /// Returns a write-only view of this slice that only writes to indices
+    /// for which `mask` returns `true`.
+    ///
+    /// Values written to other indices are dropped immediately, leaving the
+    /// element unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 5];
+    /// {
+    ///     let mut odd = WriteSlice::from(&mut buf).masked(|i| i % 2 == 1);
+    ///     for i in 0..odd.len() {
+    ///         odd.write(i, 1);
+    ///     }
+    /// }
+    /// assert_eq!(buf, [0, 1, 0, 1, 0]);
+    ///
+    /// let mut none = WriteSlice::from(&mut buf).masked(|_| false);
+    /// none.write(0, 9);
+    /// assert_eq!(buf, [0, 1, 0, 1, 0]);
+    /// # }
+    /// ```
+    pub fn masked<F: Fn(usize) -> bool>(self, mask: F) -> Masked<'a, T, F> {
+        Masked {
+            slice: self.0,
+            mask,
+        }
+    }
+
+    /// Returns an iterator over write-only references to the elements at
+    /// indices `start`, `start + step`, `start + 2 * step`, and so on, up to
+    /// the end of this slice.
+    ///
+    /// The iterator is empty if `start` is past the end of the slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// // Fill the green channel of interleaved RGB pixels.
+    /// let mut pixels = [0u8; 9];
+    /// for mut green in WriteSlice::from(&mut pixels).iter_write_strided(1, 3) {
+    ///     green.write(255);
+    /// }
+    /// assert_eq!(pixels, [0, 255, 0, 0, 255, 0, 0, 255, 0]);
+    ///
+    /// let mut buf = [0; 5];
+    /// for mut elem in WriteSlice::from(&mut buf).iter_write_strided(2, 2) {
+    ///     elem.write(1);
+    /// }
+    /// assert_eq!(buf, [0, 0, 1, 0, 1]);
+    /// assert_eq!(WriteSlice::from(&mut buf).iter_write_strided(6, 1).count(), 0);
+    /// # }
+    /// ```
+    pub fn iter_write_strided(
+        self,
+        start: usize,
+        step: usize,
+    ) -> impl Iterator<Item = WriteRef<'a, T>> {
+        assert!(step != 0, "stride step must be non-zero");
+        self.0.iter_mut().skip(start).step_by(step).map(WriteRef)
+    }
+
+    /// Write values computed from a running state to every element of this
+    /// slice, in order, and return the final state.
+    ///
+    /// `f` is called with the current state and each index, and returns the
+    /// next state along with the value to write at that index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 4];
+    /// let total = WriteSlice::from(&mut buf).fill_scan(0, |sum, i| {
+    ///     let sum = sum + i + 1;
+    ///     (sum, sum)
+    /// });
+    /// assert_eq!(buf, [1, 3, 6, 10]);
+    /// assert_eq!(total, 10);
+    /// # }
+    /// ```
+    pub fn fill_scan<St, F: FnMut(St, usize) -> (St, T)>(&mut self, init: St, mut f: F) -> St {
+        let mut state = init;
+        for (i, elem) in self.0.iter_mut().enumerate() {
+            let (next, val) = f(state, i);
+            *elem = val;
+            state = next;
+        }
+        state
+    }
+
+    /// Adapt this slice to accept values of another type, converting them
+    /// with `f` before they are written.
+    ///
+    /// `f` is called once for each write, not once for each element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0u32; 3];
+    /// {
+    ///     let mut bytes = WriteSlice::from(&mut buf).contramap(u32::from);
+    ///     bytes.write(0, 255u8);
+    ///     bytes.write(2, 7u8);
+    /// }
+    /// assert_eq!(buf, [255, 0, 7]);
+    /// # }
+    /// ```
+    pub fn contramap<U, F: FnMut(U) -> T>(self, f: F) -> MapSlice<'a, T, F> {
+        MapSlice { slice: self.0, f }
+    }
+
+    /// Split this slice into `N` disjoint pieces whose lengths differ by at
+    /// most one.
+    ///
+    /// If the length isn't a multiple of `N`, the earlier pieces are one
+    /// element longer than the later ones. The pieces can be sent to other
+    /// threads if `T` is `Send`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use std::thread;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 8];
+    /// let lens = WriteSlice::from(&mut buf).partition::<3>().map(|piece| piece.len());
+    /// assert_eq!(lens, [3, 3, 2]);
+    ///
+    /// thread::scope(|s| {
+    ///     let pieces = WriteSlice::from(&mut buf).partition::<3>();
+    ///     for (i, mut piece) in (0..).zip(pieces) {
+    ///         s.spawn(move || piece.fill(i));
+    ///     }
+    /// });
+    /// assert_eq!(buf, [0, 0, 0, 1, 1, 1, 2, 2]);
+    /// # }
+    /// ```
+    pub fn partition<const N: usize>(self) -> [WriteSlice<'a, T>; N] {
+        assert!(N != 0, "cannot partition a slice into 0 pieces");
+        let len = self.0.len();
+        let mut rest = self.0;
+        core::array::from_fn(|i| {
+            let size = len / N + usize::from(i < len % N);
+            let (piece, tail) = mem::take(&mut rest).split_at_mut(size);
+            rest = tail;
+            WriteSlice(piece)
+        })
+    }
+
+    /// Copy the elements in `src` to the part of this slice starting at
+    /// `dest`, like `[T]::copy_within`.
+    ///
+    /// The ranges may overlap. This reads the elements it copies, but their
+    /// values never leave the slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` is out of bounds, or if `dest` is too close to the end
+    /// of the slice to hold `src.len()` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [1, 2, 3, 4, 5];
+    /// WriteSlice::from(&mut buf).copy_within(0..3, 2);
+    /// assert_eq!(buf, [1, 2, 1, 2, 3]);
+    /// WriteSlice::from(&mut buf).copy_within(2..5, 0);
+    /// assert_eq!(buf, [1, 2, 3, 2, 3]);
+    /// # }
+    /// ```
+    pub fn copy_within(&mut self, src: Range<usize>, dest: usize)
+    where
+        T: Copy,
+    {
+        self.0.copy_within(src, dest);
+    }
+}
+
+impl<'a, T: 'a> From<&'a mut [T]> for WriteSlice<'a, T> {
+    fn from(inner: &'a mut [T]) -> Self {
+        WriteSlice(inner)
+    }
+}
+
+/// Allows passing a mutable reference to an array directly.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::WriteSlice;
+/// fn copy<'a, T: Clone + 'a>(input: &[T], output: impl Into<WriteSlice<'a, T>>) {
+///     let mut output = output.into();
+///     for (i, val) in input.iter().enumerate() {
+///         output.write(i, val.clone());
+///     }
+/// }
+/// fn main() {
+///     let input = [1, 2, 3];
+///     let mut output = [7, 1, 9];
+///     copy(&input, &mut output);
+///     assert_eq!(input, output);
+/// }
+/// ```
+impl<'a, T: 'a, const N: usize> From<&'a mut [T; N]> for WriteSlice<'a, T> {
+    fn from(inner: &'a mut [T; N]) -> Self {
+        WriteSlice(inner)
+    }
+}
+
+/// The error returned when a source doesn't have the same length as the
+/// `WriteSlice` it is written to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LengthMismatch;
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("source length does not match destination length")
+    }
+}
+
+impl Error for LengthMismatch {}
+
+/// The error returned when an iterator doesn't yield exactly as many items as
+/// the `WriteSlice` it is written to has elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthError {
+    /// The iterator ran out of items before the slice was full.
+    TooShort,
+    /// The iterator had items left after the slice was full.
+    TooLong,
+}
+
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            LengthError::TooShort => "iterator is shorter than destination",
+            LengthError::TooLong => "iterator is longer than destination",
+        })
+    }
+}
+
+impl Error for LengthError {}
+
+/// A write-only reference to a `Cell`.
+///
+/// This is the supported way to use write-only APIs with shared interior
+/// mutability, since a `WriteRef` requires a mutable reference.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use std::cell::Cell;
+/// # use write_ref::CellWrite;
+/// fn clear<'a>(output: impl Into<CellWrite<'a, u32>>) {
+///     output.into().write(0);
+/// }
+/// fn main() {
+///     let config = Cell::new(5);
+///     let shared = &config;
+///     clear(shared);
+///     clear(CellWrite::new(shared));
+///     assert_eq!(config.get(), 0);
+/// }
+/// ```
+pub struct CellWrite<'a, T: 'a>(&'a Cell<T>);
+
+impl<'a, T: 'a> CellWrite<'a, T> {
+    /// Create a write-only reference to a `Cell`.
+    pub fn new(inner: &'a Cell<T>) -> Self {
+        CellWrite(inner)
+    }
+
+    /// Write a value to the cell.
+    pub fn write(&mut self, val: T) {
+        self.0.set(val);
+    }
+}
+
+impl<'a, T: 'a> From<&'a Cell<T>> for CellWrite<'a, T> {
+    fn from(inner: &'a Cell<T>) -> Self {
+        CellWrite(inner)
+    }
+}
+
+/// A write-only sink that ignores everything written to it.
+///
+/// This is useful for testing or benchmarking code that produces values
+/// without needing any storage for them. Written values are dropped
+/// immediately, so their destructors still run.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::Discard;
+/// # fn main() {
+/// let mut sink = Discard::new();
+/// sink.write(String::from("dropped"));
+/// # }
+/// ```
+pub struct Discard<T>(PhantomData<fn(T)>);
+
+impl<T> Discard<T> {
+    /// Create a new `Discard`.
+    ///
+    /// This can be used in `const` and `static` initializers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::{Discard, DynWriteRef, IntoWriteRef};
+    /// const SINK: Discard<u32> = Discard::new();
+    /// fn produce(output: impl IntoWriteRef<u32>) {
+    ///     output.into_write_ref().write_dyn(1);
+    /// }
+    /// fn main() {
+    ///     produce(SINK);
+    /// }
+    /// ```
+    pub const fn new() -> Self {
+        Discard(PhantomData)
+    }
+
+    /// Drop a value.
+    pub fn write(&mut self, val: T) {
+        drop(val);
+    }
+}
+
+impl<T> Clone for Discard<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Discard<T> {}
+
+impl<T> Default for Discard<T> {
+    fn default() -> Self {
+        Discard::new()
+    }
+}
+
+/// A write-only sink that calls a closure with every value written to it.
+///
+/// This is returned by [`from_fn`](fn.from_fn.html).
+pub struct FromFn<F>(F);
+
+impl<F> FromFn<F> {
+    /// Pass a value to the closure.
+    pub fn write<T>(&mut self, val: T)
+    where
+        F: FnMut(T),
+    {
+        (self.0)(val);
+    }
+}
+
+/// Create a write-only sink that calls `f` with every value written to it.
+///
+/// This allows running side effects on each write, such as pushing to a
+/// collection or sending on a channel, without defining a new type.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use std::sync::mpsc;
+/// # fn main() {
+/// let (tx, rx) = mpsc::channel();
+/// let mut sink = write_ref::from_fn(|v| tx.send(v).unwrap());
+/// sink.write(1);
+/// sink.write(2);
+/// assert_eq!(rx.try_iter().collect::<Vec<_>>(), [1, 2]);
+/// # }
+/// ```
+pub fn from_fn<T, F: FnMut(T)>(f: F) -> FromFn<F> {
+    FromFn(f)
+}
+
+/// A write-only sink that writes every value to two sinks.
+///
+/// This is returned by [`tee`](fn.tee.html).
+pub struct Tee<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> Tee<A, B> {
+    /// Write a clone of a value to the first sink, then write the value
+    /// itself to the second.
+    pub fn write<T: Clone>(&mut self, val: T)
+    where
+        A: DynWriteRef<T>,
+        B: DynWriteRef<T>,
+    {
+        self.a.write_dyn(val.clone());
+        self.b.write_dyn(val);
+    }
+}
+
+/// Create a write-only sink that writes every value to both `a` and `b`.
+///
+/// Each write clones the value once. The clone is written to `a` first, and
+/// then the original value is written to `b`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use std::cell::Cell;
+/// struct Counted<'c>(&'c Cell<usize>);
+/// impl<'c> Clone for Counted<'c> {
+///     fn clone(&self) -> Self {
+///         self.0.set(self.0.get() + 1);
+///         Counted(self.0)
+///     }
+/// }
+/// fn main() {
+///     let clones = Cell::new(0);
+///     let mut a = Counted(&clones);
+///     let mut b = Counted(&clones);
+///     {
+///         let mut both = write_ref::tee(&mut a, &mut b);
+///         both.write(Counted(&clones));
+///         both.write(Counted(&clones));
+///     }
+///     assert_eq!(clones.get(), 2);
+///
+///     let mut x = 0;
+///     let mut y = 0;
+///     write_ref::tee(&mut x, &mut y).write(5);
+///     assert_eq!((x, y), (5, 5));
+/// }
+/// ```
+///
+/// `try_write` stops at the first sink that fails:
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::{SeqWrite, TeeError, TryWriteRef};
+/// # fn main() {
+/// let mut a = [0; 2];
+/// let mut b = [0; 1];
+/// let mut both = write_ref::tee(SeqWrite::new(&mut a), SeqWrite::new(&mut b));
+/// assert_eq!(both.try_write(1), Ok(()));
+/// assert_eq!(both.try_write(2), Err(TeeError::Second(2)));
+/// # }
+/// ```
+pub fn tee<T: Clone, A: IntoWriteRef<T>, B: IntoWriteRef<T>>(
+    a: A,
+    b: B,
+) -> Tee<A::Target, B::Target> {
+    Tee {
+        a: a.into_write_ref(),
+        b: b.into_write_ref(),
+    }
+}
+
+/// The error returned by `Tee::try_write`, saying which of the two sinks
+/// failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TeeError<A, B> {
+    /// The first sink failed, so nothing was written to the second.
+    First(A),
+    /// The second sink failed after the first one was written to.
+    Second(B),
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for TeeError<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TeeError::First(ref e) => write!(f, "first sink of tee failed: {}", e),
+            TeeError::Second(ref e) => write!(f, "second sink of tee failed: {}", e),
+        }
+    }
+}
+
+impl<A: Error, B: Error> Error for TeeError<A, B> {}
+
+/// A write-only reference that may be written to at most once.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::WriteOnce;
+/// # fn main() {
+/// let mut a = 0;
+/// {
+///     let mut a_ref = WriteOnce::new(&mut a);
+///     assert!(!a_ref.was_written());
+///     a_ref.write(5);
+///     assert!(a_ref.was_written());
+/// }
+/// assert_eq!(a, 5);
+/// # }
+/// ```
+///
+/// ```should_panic
+/// # extern crate write_ref;
+/// # use write_ref::WriteOnce;
+/// # fn main() {
+/// let mut a = 0;
+/// let mut a_ref = WriteOnce::new(&mut a);
+/// a_ref.write(5);
+/// a_ref.write(6); // panics
+/// # }
+/// ```
+pub struct WriteOnce<'a, T: 'a> {
+    dst: &'a mut T,
+    written: bool,
+}
+
+impl<'a, T: 'a> WriteOnce<'a, T> {
+    /// Create a reference that may be written to at most once.
+    pub fn new(dst: &'a mut T) -> Self {
+        WriteOnce {
+            dst,
+            written: false,
+        }
+    }
+
+    /// Write a value to this reference.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this reference has already been written to.
+    pub fn write(&mut self, val: T) {
+        assert!(!self.written, "WriteOnce was written to more than once");
+        *self.dst = val;
+        self.written = true;
+    }
+
+    /// Write the value returned by `f` to this reference.
+    ///
+    /// # Panics
+    ///
+    /// Panics without calling `f` if this reference has already been written
+    /// to.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// # extern crate write_ref;
+    /// # use write_ref::WriteOnce;
+    /// # fn main() {
+    /// let mut a = 0;
+    /// let mut a_ref = WriteOnce::new(&mut a);
+    /// a_ref.write_with(|| 5);
+    /// a_ref.write_with(|| unreachable!()); // panics before calling the closure
+    /// # }
+    /// ```
+    pub fn write_with<F: FnOnce() -> T>(&mut self, f: F) {
+        assert!(!self.written, "WriteOnce was written to more than once");
+        *self.dst = f();
+        self.written = true;
+    }
+
+    /// Returns `true` if this reference has been written to.
+    pub fn was_written(&self) -> bool {
+        self.written
+    }
+
+    /// Chain another sink after this one, so that the first write goes to
+    /// this reference and every later write goes to `next`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::{WriteOnce, WriteRef};
+    /// # fn main() {
+    /// let mut head = 0;
+    /// let mut tail = 0;
+    /// {
+    ///     let mut chain = WriteOnce::new(&mut head).and_then(WriteRef::from(&mut tail));
+    ///     chain.write(1);
+    ///     chain.write(2);
+    ///     chain.write(3);
+    /// }
+    /// assert_eq!((head, tail), (1, 3));
+    /// # }
+    /// ```
+    pub fn and_then<W: DynWriteRef<T>>(self, next: W) -> Chain<'a, T, W> {
+        Chain { head: self, next }
+    }
+}
+
+/// A write-only reference that must be written to before it is dropped.
+///
+/// This catches code that forgets to fill in an out-parameter. The check
+/// happens in `Drop`, so it is skipped if the reference is leaked with
+/// `mem::forget`. With the `std` feature, it is also skipped if the thread is
+/// already panicking.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::MustWrite;
+/// # fn main() {
+/// let mut a = 0;
+/// MustWrite::new(&mut a).write(5);
+/// assert_eq!(a, 5);
+/// # }
+/// ```
+///
+/// ```should_panic
+/// # extern crate write_ref;
+/// # use write_ref::MustWrite;
+/// # fn main() {
+/// let mut a = 0;
+/// let a_ref = MustWrite::new(&mut a);
+/// drop(a_ref); // panics
+/// # }
+/// ```
+pub struct MustWrite<'a, T: 'a> {
+    dst: &'a mut T,
+    written: bool,
+}
+
+impl<'a, T: 'a> MustWrite<'a, T> {
+    /// Create a reference that must be written to before it is dropped.
+    pub fn new(dst: &'a mut T) -> Self {
+        MustWrite {
+            dst,
+            written: false,
+        }
+    }
+
+    /// Write a value to this reference.
+    pub fn write(&mut self, val: T) {
+        *self.dst = val;
+        self.written = true;
+    }
+}
+
+impl<'a, T: 'a> Drop for MustWrite<'a, T> {
+    fn drop(&mut self) {
+        if !self.written && !panicking() {
+            panic!("MustWrite was dropped without being written to");
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn panicking() -> bool {
+    std::thread::panicking()
+}
+
+#[cfg(not(feature = "std"))]
+fn panicking() -> bool {
+    false
+}
+
+/// Panics with the same message as slice indexing if `idx` is out of bounds
+/// for a length of `len`.
+#[track_caller]
+fn check_index(idx: usize, len: usize) {
+    assert!(
+        idx < len,
+        "index out of bounds: the len is {} but the index is {}",
+        len,
+        idx
+    );
+}
+
+/// A write-only sink that counts how many times it was written to.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::{CountingWrite, SeqWrite, TryWriteRef};
+/// # fn main() {
+/// let mut a = 0;
+/// {
+///     let mut a_ref = CountingWrite::new(&mut a);
+///     a_ref.write(1);
+///     a_ref.write(2);
+///     assert_eq!(a_ref.count(), 2);
+/// }
+/// assert_eq!(a, 2);
+///
+/// let mut buf = [0; 1];
+/// let mut sink = CountingWrite::new(SeqWrite::new(&mut buf));
+/// assert_eq!(sink.try_write(1), Ok(()));
+/// assert_eq!(sink.try_write(2), Err(2));
+/// assert_eq!(sink.count(), 1);
+/// # }
+/// ```
+pub struct CountingWrite<W> {
+    inner: W,
+    count: usize,
+}
+
+impl<W> CountingWrite<W> {
+    /// Wrap a sink so that writes to it are counted.
+    pub fn new<T>(inner: impl IntoWriteRef<T, Target = W>) -> Self {
+        CountingWrite {
+            inner: inner.into_write_ref(),
+            count: 0,
+        }
+    }
+
+    /// Write a value to the underlying sink.
+    pub fn write<T>(&mut self, val: T)
+    where
+        W: DynWriteRef<T>,
+    {
+        self.inner.write_dyn(val);
+        self.count += 1;
+    }
+
+    /// Returns the number of writes so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// A write-only buffer that counts how many times it was written to.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::CountingWriteSlice;
+/// # fn main() {
+/// let mut buf = [0; 2];
+/// {
+///     let mut slice = CountingWriteSlice::new(&mut buf as &mut [_]);
+///     slice.write(0, 1);
+///     slice.write(1, 2);
+///     slice.write(1, 3);
+///     assert_eq!(slice.count(), 3);
+/// }
+/// assert_eq!(buf, [1, 3]);
+/// # }
+/// ```
+pub struct CountingWriteSlice<'a, T: 'a> {
+    inner: WriteSlice<'a, T>,
+    count: usize,
+}
+
+impl<'a, T: 'a> CountingWriteSlice<'a, T> {
+    /// Wrap a write-only buffer so that writes to it are counted.
+    pub fn new(inner: impl Into<WriteSlice<'a, T>>) -> Self {
+        CountingWriteSlice {
+            inner: inner.into(),
+            count: 0,
+        }
+    }
+
+    /// Write a value to an element of the underlying buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn write(&mut self, idx: usize, val: T) {
+        self.inner.write(idx, val);
+        self.count += 1;
+    }
+
+    /// Returns the number of writes so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// An object-safe trait for write-only sinks.
+///
+/// `WriteRef` is a concrete type that only wraps a `&mut T`, so it can't be
+/// used to store different kinds of sinks together. This trait is implemented
+/// by `WriteRef` and by every other sink in this crate, which allows mixing
+/// them behind `dyn DynWriteRef<T>`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use std::cell::Cell;
+/// # use write_ref::{CellWrite, Discard, DynWriteRef, WriteRef};
+/// # fn main() {
+/// let mut a = 0;
+/// let b = Cell::new(0);
+/// {
+///     let mut sinks: Vec<Box<dyn DynWriteRef<i32>>> = vec![
+///         Box::new(WriteRef::from(&mut a)),
+///         Box::new(CellWrite::new(&b)),
+///         Box::new(Discard::new()),
+///     ];
+///     for sink in &mut sinks {
+///         sink.write_dyn(7);
+///     }
+/// }
+/// assert_eq!((a, b.get()), (7, 7));
+/// # }
+/// ```
+pub trait DynWriteRef<T> {
+    /// Write a value to this sink.
+    fn write_dyn(&mut self, value: T);
+
+    /// Borrow this sink so that it can be passed to a function that takes a
+    /// sink by value, and still be used afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::{CountingWrite, DynWriteRef, IntoWriteRef};
+    /// fn produce(output: impl IntoWriteRef<u32>) {
+    ///     let mut output = output.into_write_ref();
+    ///     output.write_dyn(1);
+    ///     output.write_dyn(2);
+    /// }
+    /// fn main() {
+    ///     let mut a = 0;
+    ///     let mut counted = CountingWrite::new(&mut a);
+    ///     produce(counted.by_ref());
+    ///     assert_eq!(counted.count(), 2);
+    /// }
+    /// ```
+    fn by_ref(&mut self) -> ByRef<'_, Self>
+    where
+        Self: Sized,
+    {
+        ByRef(self)
+    }
+
+    /// Write a value to this sink `n` times.
+    ///
+    /// The effect depends on the sink. A `WriteRef` ends up holding a single
+    /// copy of the value, while a sequential sink such as `SeqWrite` gets `n`
+    /// copies of it. The value is cloned for every write but the last.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::{DynWriteRef, SeqWrite, WriteRef};
+    /// # fn main() {
+    /// let mut buf = [' '; 3];
+    /// SeqWrite::new(&mut buf).write_n(3, 'x');
+    /// assert_eq!(buf, ['x', 'x', 'x']);
+    ///
+    /// let mut a = ' ';
+    /// WriteRef::from(&mut a).write_n(3, 'x');
+    /// assert_eq!(a, 'x');
+    /// # }
+    /// ```
+    fn write_n(&mut self, n: usize, value: T)
+    where
+        Self: Sized,
+        T: Clone,
+    {
+        if n == 0 {
+            return;
+        }
+        for _ in 1..n {
+            self.write_dyn(value.clone());
+        }
+        self.write_dyn(value);
+    }
+}
+
+macro_rules! infallible_sink {
+    ($($(#[$attr:meta])* [$($gen:tt)*] $sink:ty => $t:ty;)*) => {$(
+        $(#[$attr])*
+        impl<$($gen)*> DynWriteRef<$t> for $sink {
+            fn write_dyn(&mut self, value: $t) {
+                self.write(value);
+            }
+        }
+
+        $(#[$attr])*
+        impl<$($gen)*> TryWriteRef<$t> for $sink {
+            type Error = Infallible;
+
+            fn try_write(&mut self, value: $t) -> Result<(), Infallible> {
+                self.write(value);
+                Ok(())
+            }
+        }
+
+        $(#[$attr])*
+        impl<$($gen)*> IntoWriteRef<$t> for $sink {
+            type Target = Self;
+
+            fn into_write_ref(self) -> Self {
+                self
+            }
+        }
+    )*};
+}
+
+infallible_sink! {
+    ['a, T: 'a] WriteRef<'a, T> => T;
+    ['a, T: 'a, U, F: FnMut(U) -> T] MapWrite<'a, T, F> => U;
+    ['a, T: 'a] CellWrite<'a, T> => T;
+    [T] Discard<T> => T;
+    [T, F: FnMut(T)] FromFn<F> => T;
+    ['a, T: 'a] MustWrite<'a, T> => T;
+    ['a, T: 'a] ReplaceRef<'a, T> => T;
+}
+
+/// Writes to the borrowed sink, so that a function taking a sink by value
+/// can be passed `&mut sink` or a `&mut dyn DynWriteRef<T>`.
+///
+/// Functions that take an `impl IntoWriteRef<T>` can't accept this, since
+/// `&mut T` converts to a `WriteRef<T>` there. Use
+/// [`by_ref`](trait.DynWriteRef.html#method.by_ref) for those instead.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::{DynWriteRef, WriteRef};
+/// fn produce(mut output: impl DynWriteRef<u32>) {
+///     output.write_dyn(5);
+/// }
+/// fn main() {
+///     let mut a = 0;
+///     let mut b = 0;
+///     let mut c = 0;
+///     let mut a_ref = WriteRef::from(&mut a);
+///     produce(&mut a_ref);
+///     let mut b_ref = WriteRef::from(&mut b);
+///     produce(&mut &mut b_ref);
+///     let mut c_ref = WriteRef::from(&mut c);
+///     let dyn_ref: &mut dyn DynWriteRef<u32> = &mut c_ref;
+///     produce(dyn_ref);
+///     assert_eq!((a, b, c), (5, 5, 5));
+/// }
+/// ```
+impl<T, W: DynWriteRef<T> + ?Sized> DynWriteRef<T> for &mut W {
+    fn write_dyn(&mut self, value: T) {
+        (**self).write_dyn(value);
+    }
+}
+
+impl<T, W: TryWriteRef<T> + ?Sized> TryWriteRef<T> for &mut W {
+    type Error = W::Error;
+
+    fn try_write(&mut self, value: T) -> Result<(), W::Error> {
+        (**self).try_write(value)
+    }
+}
+
+impl<T: Clone, A: DynWriteRef<T>, B: DynWriteRef<T>> DynWriteRef<T> for Tee<A, B> {
+    fn write_dyn(&mut self, value: T) {
+        self.write(value);
+    }
+}
+
+/// Fails with the error of the first sink that fails. Nothing is written to
+/// the second sink if the first one fails.
+impl<T: Clone, A: TryWriteRef<T>, B: TryWriteRef<T>> TryWriteRef<T> for Tee<A, B> {
+    type Error = TeeError<A::Error, B::Error>;
+
+    fn try_write(&mut self, value: T) -> Result<(), TeeError<A::Error, B::Error>> {
+        self.a.try_write(value.clone()).map_err(TeeError::First)?;
+        self.b.try_write(value).map_err(TeeError::Second)
+    }
+}
+
+impl<'a, T: 'a> DynWriteRef<T> for WriteOnce<'a, T> {
+    fn write_dyn(&mut self, value: T) {
+        self.write(value);
+    }
+}
+
+/// Fails if the reference was already written to, returning the value.
+impl<'a, T: 'a> TryWriteRef<T> for WriteOnce<'a, T> {
+    type Error = T;
+
+    fn try_write(&mut self, value: T) -> Result<(), T> {
+        if self.written {
+            return Err(value);
+        }
+        self.write(value);
+        Ok(())
+    }
+}
+
+impl<T, W: DynWriteRef<T>> DynWriteRef<T> for CountingWrite<W> {
+    fn write_dyn(&mut self, value: T) {
+        self.write(value);
+    }
+}
+
+/// Fails if the underlying sink fails. Failed writes aren't counted.
+impl<T, W: TryWriteRef<T>> TryWriteRef<T> for CountingWrite<W> {
+    type Error = W::Error;
+
+    fn try_write(&mut self, value: T) -> Result<(), W::Error> {
+        self.inner.try_write(value)?;
+        self.count += 1;
+        Ok(())
+    }
+}
+
+/// A reference that can be written to and returns the previous value when it
+/// is replaced.
+///
+/// Unlike `WriteRef`, this lets the holder observe the old contents through
+/// `replace`, so taking an `impl Into<ReplaceRef>` is a weaker promise than
+/// taking an `impl Into<WriteRef>`. It should only be used when the previous
+/// value is actually needed, such as to reclaim a buffer.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::ReplaceRef;
+/// # fn main() {
+/// let mut a = vec![1, 2];
+/// {
+///     let mut a_ref = ReplaceRef::from(&mut a);
+///     assert_eq!(a_ref.replace(vec![3]), [1, 2]);
+///     a_ref.write(vec![4]);
+/// }
+/// assert_eq!(a, [4]);
+/// # }
+/// ```
+pub struct ReplaceRef<'a, T: 'a>(&'a mut T);
+
+impl<'a, T: 'a> ReplaceRef<'a, T> {
+    /// Write a value to this reference.
+    pub fn write(&mut self, val: T) {
+        *self.0 = val;
+    }
+
+    /// Write a value to this reference, returning the previous value.
+    pub fn replace(&mut self, val: T) -> T {
+        mem::replace(self.0, val)
+    }
+}
+
+impl<'a, T: 'a> From<&'a mut T> for ReplaceRef<'a, T> {
+    fn from(inner: &'a mut T) -> Self {
+        ReplaceRef(inner)
+    }
+}
+
+/// A write-only buffer that writes every value to two slices.
+///
+/// This is returned by [`tee_slice`](fn.tee_slice.html).
+pub struct TeeSlice<'a, T: 'a> {
+    a: WriteSlice<'a, T>,
+    b: WriteSlice<'a, T>,
+}
+
+impl<'a, T: Clone + 'a> TeeSlice<'a, T> {
+    /// Write a clone of a value to an element of the first slice, then write
+    /// the value itself to the same element of the second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds. Since both slices have the same
+    /// length, nothing is written in this case.
+    pub fn write(&mut self, idx: usize, val: T) {
+        self.a.write(idx, val.clone());
+        self.b.write(idx, val);
+    }
+
+    /// Returns the number of elements in each slice.
+    pub fn len(&self) -> usize {
+        self.a.len()
+    }
+
+    /// Returns `true` if the slices have a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.a.is_empty()
+    }
+}
+
+/// Create a write-only buffer that writes every value to the same element of
+/// both `a` and `b`.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # fn main() {
+/// let mut work = [0; 3];
+/// let mut staging = [0; 3];
+/// {
+///     let mut both = write_ref::tee_slice(&mut work, &mut staging);
+///     for i in 0..both.len() {
+///         both.write(i, i + 1);
+///     }
+/// }
+/// assert_eq!(work, [1, 2, 3]);
+/// assert_eq!(staging, work);
+/// # }
+/// ```
+///
+/// ```should_panic
+/// # extern crate write_ref;
+/// # fn main() {
+/// let mut work = [0; 3];
+/// let mut staging = [0; 3];
+/// write_ref::tee_slice(&mut work, &mut staging).write(3, 1);
+/// # }
+/// ```
+pub fn tee_slice<'a, T: Clone + 'a>(
+    a: impl Into<WriteSlice<'a, T>>,
+    b: impl Into<WriteSlice<'a, T>>,
+) -> TeeSlice<'a, T> {
+    let a = a.into();
+    let b = b.into();
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "tee_slice requires slices of equal length"
+    );
+    TeeSlice { a, b }
+}
+
+/// A write-only view of a slice in reverse order.
+///
+/// This is returned by [`WriteSlice::reversed`](struct.WriteSlice.html#method.reversed).
+pub struct Reversed<'a, T: 'a>(&'a mut [T]);
+
+impl<'a, T: 'a> Reversed<'a, T> {
+    /// Write a value to the element `idx` positions from the end of the
+    /// slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn write(&mut self, idx: usize, val: T) {
+        let len = self.0.len();
+        check_index(idx, len);
+        self.0[len - 1 - idx] = val;
+    }
+
+    /// Returns the number of elements in this slice.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if this slice has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// A write-only view of every `step`th element of a slice.
+///
+/// This is returned by [`WriteSlice::stride`](struct.WriteSlice.html#method.stride).
+pub struct Stride<'a, T: 'a> {
+    slice: &'a mut [T],
+    step: usize,
+}
+
+impl<'a, T: 'a> Stride<'a, T> {
+    /// Write a value to the element at `idx * step` in the underlying slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn write(&mut self, idx: usize, val: T) {
+        check_index(idx, self.len());
+        self.slice[idx * self.step] = val;
+    }
+
+    /// Returns the number of elements in this view.
+    pub fn len(&self) -> usize {
+        self.slice.len().div_ceil(self.step)
+    }
+
+    /// Returns `true` if this view has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.slice.is_empty()
+    }
+}
+
+/// A write-only buffer of pairs whose components are written to two slices.
+///
+/// This is returned by [`zip_slices`](fn.zip_slices.html).
+pub struct ZipSlices<'a, A: 'a, B: 'a> {
+    a: WriteSlice<'a, A>,
+    b: WriteSlice<'a, B>,
+}
+
+impl<'a, A: 'a, B: 'a> ZipSlices<'a, A, B> {
+    /// Write the first component of a pair to an element of the first slice,
+    /// and the second component to the same element of the second slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds. Nothing is written in this case.
+    pub fn write(&mut self, idx: usize, (x, y): (A, B)) {
+        check_index(idx, self.len());
+        self.a.write(idx, x);
+        self.b.write(idx, y);
+    }
+
+    /// Returns the number of elements in the shorter of the two slices.
+    pub fn len(&self) -> usize {
+        self.a.len().min(self.b.len())
+    }
+
+    /// Returns `true` if either slice has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Create a write-only buffer of pairs that writes the first component of
+/// each pair to `a` and the second to `b`.
+///
+/// The length of the result is the length of the shorter slice. Elements of
+/// the longer slice past that length can't be written to.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # fn main() {
+/// let mut xs = [0; 3];
+/// let mut ys = [' '; 2];
+/// {
+///     let mut pairs = write_ref::zip_slices(&mut xs, &mut ys);
+///     assert_eq!(pairs.len(), 2);
+///     pairs.write(0, (1, 'a'));
+///     pairs.write(1, (2, 'b'));
+/// }
+/// assert_eq!(xs, [1, 2, 0]);
+/// assert_eq!(ys, ['a', 'b']);
+/// # }
+/// ```
+pub fn zip_slices<'a, A: 'a, B: 'a>(
+    a: impl Into<WriteSlice<'a, A>>,
+    b: impl Into<WriteSlice<'a, B>>,
+) -> ZipSlices<'a, A, B> {
+    ZipSlices {
+        a: a.into(),
+        b: b.into(),
+    }
+}
+
+/// A write-only reference to possibly uninitialized storage.
+///
+/// Writing initializes the storage, but the caller that owns the
+/// `MaybeUninit` is still responsible for calling `assume_init` once it knows
+/// a write has happened. Writing again doesn't drop the previous value.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use std::mem::MaybeUninit;
+/// # use write_ref::UninitWrite;
+/// # fn main() {
+/// let mut slot = MaybeUninit::uninit();
+/// UninitWrite::from(&mut slot).write(String::from("init"));
+/// let value = unsafe { slot.assume_init() };
+/// assert_eq!(value, "init");
+/// # }
+/// ```
+pub struct UninitWrite<'a, T: 'a>(&'a mut MaybeUninit<T>);
+
+impl<'a, T: 'a> UninitWrite<'a, T> {
+    /// Initialize the storage with a value.
+    pub fn write(&mut self, val: T) {
+        self.0.write(val);
+    }
+}
+
+impl<'a, T: 'a> From<&'a mut MaybeUninit<T>> for UninitWrite<'a, T> {
+    fn from(inner: &'a mut MaybeUninit<T>) -> Self {
+        UninitWrite(inner)
+    }
+}
+
+infallible_sink! {
+    ['a, T: 'a] UninitWrite<'a, T> => T;
+}
+
+/// A write-only buffer of possibly uninitialized elements.
+///
+/// Each write initializes one element. Elements that were never written stay
+/// uninitialized, so the caller must only call `assume_init` on elements it
+/// knows were written. Writing an element again doesn't drop its previous
+/// value.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use std::mem::MaybeUninit;
+/// # use write_ref::UninitWriteSlice;
+/// # fn main() {
+/// let mut buf = [MaybeUninit::<u8>::uninit(); 3];
+/// {
+///     let mut slice = UninitWriteSlice::from(&mut buf);
+///     for i in 0..slice.len() {
+///         slice.write(i, i as u8 * 2);
+///     }
+/// }
+/// let buf = buf.map(|elem| unsafe { elem.assume_init() });
+/// assert_eq!(buf, [0, 2, 4]);
+/// # }
+/// ```
+pub struct UninitWriteSlice<'a, T: 'a>(&'a mut [MaybeUninit<T>]);
+
+impl<'a, T: 'a> UninitWriteSlice<'a, T> {
+    /// Initialize an element of this slice with a value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn write(&mut self, idx: usize, val: T) {
+        self.0[idx].write(val);
+    }
+
+    /// Returns the number of elements in this slice.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if this slice has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Convert this slice into a slice of initialized elements.
+    ///
+    /// The elements are still owned by the underlying `MaybeUninit` buffer,
+    /// so they won't be dropped when it goes out of scope.
+    ///
+    /// # Safety
+    ///
+    /// Every element of this slice must have been written to. Use
+    /// [`Tracked::try_assume_init`](struct.Tracked.html#method.try_assume_init)
+    /// to have this checked instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use std::mem::MaybeUninit;
+    /// # use write_ref::UninitWriteSlice;
+    /// # fn main() {
+    /// let mut buf = [MaybeUninit::<u8>::uninit(); 3];
+    /// let mut slice = UninitWriteSlice::from(&mut buf);
+    /// for i in 0..slice.len() {
+    ///     slice.write(i, i as u8 + 1);
+    /// }
+    /// let init = unsafe { slice.assume_init() };
+    /// assert_eq!(init, [1, 2, 3]);
+    /// # }
+    /// ```
+    pub unsafe fn assume_init(self) -> &'a mut [T] {
+        &mut *(self.0 as *mut [MaybeUninit<T>] as *mut [T])
+    }
+}
+
+impl<'a, T: 'a> From<&'a mut [MaybeUninit<T>]> for UninitWriteSlice<'a, T> {
+    fn from(inner: &'a mut [MaybeUninit<T>]) -> Self {
+        UninitWriteSlice(inner)
+    }
+}
+
+impl<'a, T: 'a, const N: usize> From<&'a mut [MaybeUninit<T>; N]> for UninitWriteSlice<'a, T> {
+    fn from(inner: &'a mut [MaybeUninit<T>; N]) -> Self {
+        UninitWriteSlice(inner)
+    }
+}
+
+/// Create a write-only sink from a mutable reference, optionally adapting it
+/// with a combinator.
+///
+/// The grammar is:
+///
+/// - `write_ref!(dst)` creates a `WriteRef`.
+/// - `write_ref!(dst => map f)` is `WriteRef::from(dst).contramap(f)`.
+/// - `write_ref!(dst => tee other)` is `write_ref::tee(dst, other)`.
+/// - `write_ref!(dst => count)` is `CountingWrite::new(dst)`.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate write_ref;
+/// # fn main() {
+/// let mut a = 0;
+/// let mut b = 0;
+/// write_ref!(&mut a).write(1);
+/// write_ref!(&mut a => map |v: u8| v as i32 + 1).write(1);
+/// assert_eq!(a, 2);
+/// write_ref!(&mut a => tee &mut b).write(3);
+/// assert_eq!((a, b), (3, 3));
+/// let mut counted = write_ref!(&mut a => count);
+/// counted.write(4);
+/// assert_eq!(counted.count(), 1);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! write_ref {
+    ($dst:expr) => {
+        $crate::WriteRef::from($dst)
+    };
+    ($dst:expr => map $f:expr) => {
+        $crate::WriteRef::from($dst).contramap($f)
+    };
+    ($dst:expr => tee $other:expr) => {
+        $crate::tee($dst, $other)
+    };
+    ($dst:expr => count) => {
+        $crate::CountingWrite::new($dst)
+    };
+}
+
+/// Create a write-only buffer from a mutable slice or array reference,
+/// optionally adapting it with a combinator.
+///
+/// The grammar is:
+///
+/// - `write_slice!(dst)` creates a `WriteSlice`.
+/// - `write_slice!(dst => tee other)` is `write_ref::tee_slice(dst, other)`.
+/// - `write_slice!(dst => count)` is `CountingWriteSlice::new(dst)`.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate write_ref;
+/// # fn main() {
+/// let mut a = [0; 2];
+/// let mut b = [0; 2];
+/// write_slice!(&mut a).write(0, 1);
+/// assert_eq!(a, [1, 0]);
+/// write_slice!(&mut a => tee &mut b).write(1, 2);
+/// assert_eq!((a, b), ([1, 2], [0, 2]));
+/// let mut counted = write_slice!(&mut a => count);
+/// counted.write(0, 3);
+/// assert_eq!(counted.count(), 1);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! write_slice {
+    ($dst:expr) => {
+        $crate::WriteSlice::from($dst)
+    };
+    ($dst:expr => tee $other:expr) => {
+        $crate::tee_slice($dst, $other)
+    };
+    ($dst:expr => count) => {
+        $crate::CountingWriteSlice::new($dst)
+    };
+}
+
+/// A write-only sink whose writes can fail.
+///
+/// Every sink in this crate implements this trait. Sinks that can't fail
+/// have an `Error` of `Infallible`, while sinks that can reject a write, such
+/// as a `WriteOnce` that was already written to, return the rejected value.
+/// Sinks that wrap other sinks, such as `LoggingWrite`, implement it when the
+/// sinks they wrap do, and fail with their errors. Generic code that bounds
+/// on `TryWriteRef<T>` works with all of them.
+///
+/// There is no blanket implementation for every `DynWriteRef`, so that a
+/// sink whose `write` can panic can report the failure here instead. As a
+/// result, a `DynWriteRef` sink defined in another crate has no `try_write`,
+/// and can't be passed to code that bounds on `TryWriteRef<T>`, unless it
+/// implements this trait itself.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::{TryWriteRef, WriteOnce, WriteRef};
+/// struct Bounded {
+///     items: Vec<u8>,
+///     capacity: usize,
+/// }
+/// impl TryWriteRef<u8> for Bounded {
+///     type Error = u8;
+///     fn try_write(&mut self, value: u8) -> Result<(), u8> {
+///         if self.items.len() == self.capacity {
+///             return Err(value);
+///         }
+///         self.items.push(value);
+///         Ok(())
+///     }
+/// }
+/// fn send_all<S: TryWriteRef<u8>>(sink: &mut S, values: &[u8]) -> Result<(), S::Error> {
+///     for &v in values {
+///         sink.try_write(v)?;
+///     }
+///     Ok(())
+/// }
+/// fn main() {
+///     let mut a = 0;
+///     assert!(send_all(&mut WriteRef::from(&mut a), &[1, 2, 3]).is_ok());
+///     assert_eq!(a, 3);
+///
+///     let mut bounded = Bounded { items: Vec::new(), capacity: 2 };
+///     assert_eq!(send_all(&mut bounded, &[1, 2, 3]), Err(3));
+///     assert_eq!(bounded.items, [1, 2]);
+///
+///     let mut b = 0;
+///     assert_eq!(send_all(&mut WriteOnce::new(&mut b), &[1, 2]), Err(2));
+///     assert_eq!(b, 1);
+/// }
+/// ```
+pub trait TryWriteRef<T> {
+    /// The error returned when a write fails.
+    type Error;
+
+    /// Attempt to write a value to this sink.
+    fn try_write(&mut self, value: T) -> Result<(), Self::Error>;
+}
+
+/// A write-only reference to an `Option` that stores each written value as
+/// `Some`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::SomeWrite;
+/// # fn main() {
+/// let mut a = None;
+/// SomeWrite::new(&mut a).write(1);
+/// assert_eq!(a, Some(1));
+/// SomeWrite::new(&mut a).write(2);
+/// assert_eq!(a, Some(2));
+/// # }
+/// ```
+pub struct SomeWrite<'a, T: 'a>(&'a mut Option<T>);
+
+impl<'a, T: 'a> SomeWrite<'a, T> {
+    /// Create a write-only reference to an `Option`.
+    pub fn new(inner: &'a mut Option<T>) -> Self {
+        SomeWrite(inner)
+    }
+
+    /// Write `Some(val)` to the `Option`.
+    pub fn write(&mut self, val: T) {
+        *self.0 = Some(val);
+    }
+}
+
+impl<'a, T: 'a> From<&'a mut Option<T>> for SomeWrite<'a, T> {
+    fn from(inner: &'a mut Option<T>) -> Self {
+        SomeWrite(inner)
+    }
+}
+
+infallible_sink! {
+    ['a, T: 'a] SomeWrite<'a, T> => T;
+}
+
+/// A write-only sink that sends its first write to a `WriteOnce` and all
+/// later writes to another sink.
+///
+/// This is returned by [`WriteOnce::and_then`](struct.WriteOnce.html#method.and_then).
+pub struct Chain<'a, T: 'a, W> {
+    head: WriteOnce<'a, T>,
+    next: W,
+}
+
+impl<'a, T: 'a, W: DynWriteRef<T>> Chain<'a, T, W> {
+    /// Write a value to the head if it hasn't been written to yet, and to the
+    /// next sink otherwise.
+    pub fn write(&mut self, val: T) {
+        if self.head.was_written() {
+            self.next.write_dyn(val);
+        } else {
+            self.head.write(val);
+        }
+    }
+}
+
+impl<'a, T: 'a, W: DynWriteRef<T>> DynWriteRef<T> for Chain<'a, T, W> {
+    fn write_dyn(&mut self, value: T) {
+        self.write(value);
+    }
+}
+
+/// Fails if the next sink fails. The first write always succeeds.
+impl<'a, T: 'a, W: TryWriteRef<T>> TryWriteRef<T> for Chain<'a, T, W> {
+    type Error = W::Error;
+
+    fn try_write(&mut self, value: T) -> Result<(), W::Error> {
+        if self.head.was_written() {
+            self.next.try_write(value)
+        } else {
+            self.head.write(value);
+            Ok(())
+        }
+    }
+}
+
+/// A write-only sink that records every value written to it.
+///
+/// This is a ready-made mock for testing code that produces values. It is
+/// only available with the `testing` feature.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::ExpectWrites;
+/// # fn main() {
+/// let mut sink = ExpectWrites::new();
+/// sink.write(1);
+/// sink.write(2);
+/// sink.assert_eq(&[1, 2]);
+/// assert_eq!(sink.into_written(), [1, 2]);
+/// # }
+/// ```
+///
+/// A mismatch panics with both lists of values:
+///
+/// ```
+/// # extern crate write_ref;
+/// # use std::panic;
+/// # use write_ref::ExpectWrites;
+/// # fn main() {
+/// let mut sink = ExpectWrites::new();
+/// sink.write(1);
+/// let err = panic::catch_unwind(|| sink.assert_eq(&[2])).unwrap_err();
+/// assert_eq!(
+///     err.downcast_ref::<String>().map(String::as_str),
+///     Some("expected writes [2], got [1]")
+/// );
+/// # }
+/// ```
+#[cfg(feature = "testing")]
+pub struct ExpectWrites<T>(Vec<T>);
+
+#[cfg(feature = "testing")]
+impl<T> ExpectWrites<T> {
+    /// Create a sink that hasn't been written to.
+    pub fn new() -> Self {
+        ExpectWrites(Vec::new())
+    }
+
+    /// Record a value.
+    pub fn write(&mut self, val: T) {
+        self.0.push(val);
+    }
+
+    /// Returns the written values, in order.
+    pub fn into_written(self) -> Vec<T> {
+        self.0
+    }
+
+    /// Assert that exactly the values in `expected` were written, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics with both lists of values if they differ.
+    pub fn assert_eq(&self, expected: &[T])
+    where
+        T: PartialEq + fmt::Debug,
+    {
+        assert!(
+            self.0 == expected,
+            "expected writes {:?}, got {:?}",
+            expected,
+            self.0
+        );
+    }
+}
+
+#[cfg(feature = "testing")]
+impl<T> Default for ExpectWrites<T> {
+    fn default() -> Self {
+        ExpectWrites::new()
+    }
+}
+
+infallible_sink! {
+    #[cfg(feature = "testing")]
+    [T] ExpectWrites<T> => T;
+}
+
+/// A write-only buffer that records every write to it along with its index.
+///
+/// This is the slice counterpart of `ExpectWrites`. It is only available with
+/// the `testing` feature.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::ExpectSliceWrites;
+/// # fn main() {
+/// let mut sink = ExpectSliceWrites::new(3);
+/// sink.write(2, 'a');
+/// sink.write(0, 'b');
+/// sink.assert_eq(&[(2, 'a'), (0, 'b')]);
+/// # }
+/// ```
+#[cfg(feature = "testing")]
+pub struct ExpectSliceWrites<T> {
+    len: usize,
+    written: Vec<(usize, T)>,
+}
+
+#[cfg(feature = "testing")]
+impl<T> ExpectSliceWrites<T> {
+    /// Create a buffer of length `len` that hasn't been written to.
+    pub fn new(len: usize) -> Self {
+        ExpectSliceWrites {
+            len,
+            written: Vec::new(),
+        }
+    }
+
+    /// Record a value written to an element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn write(&mut self, idx: usize, val: T) {
+        check_index(idx, self.len);
+        self.written.push((idx, val));
+    }
+
+    /// Returns the length of this buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this buffer has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the written indices and values, in order.
+    pub fn into_written(self) -> Vec<(usize, T)> {
+        self.written
+    }
+
+    /// Assert that exactly the writes in `expected` happened, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics with both lists of writes if they differ.
+    pub fn assert_eq(&self, expected: &[(usize, T)])
+    where
+        T: PartialEq + fmt::Debug,
+    {
+        assert!(
+            self.written == expected,
+            "expected writes {:?}, got {:?}",
+            expected,
+            self.written
+        );
+    }
+}
+
+/// A write-only view of a slice rotated by an offset.
+///
+/// This is returned by [`WriteSlice::rotated`](struct.WriteSlice.html#method.rotated).
+pub struct Rotated<'a, T: 'a> {
+    slice: &'a mut [T],
+    offset: usize,
+}
+
+impl<'a, T: 'a> Rotated<'a, T> {
+    /// Write a value to the element at `(idx + offset) % len` in the
+    /// underlying slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn write(&mut self, idx: usize, val: T) {
+        let len = self.slice.len();
+        check_index(idx, len);
+        self.slice[(idx + self.offset) % len] = val;
+    }
+
+    /// Returns the number of elements in this slice.
+    pub fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    /// Returns `true` if this slice has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.slice.is_empty()
+    }
+}
+
+/// Conversion into a write-only sink.
+///
+/// This is implemented for `&mut T`, `&Cell<T>`, and every sink in this
+/// crate, so a function that takes an `impl IntoWriteRef<T>` accepts any of
+/// them and can call `into_write_ref` to get something it can write to. The
+/// trait isn't sealed, so other crates can implement it for their own types
+/// as long as the target implements `DynWriteRef<T>`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::{Discard, DynWriteRef, IntoWriteRef};
+/// fn produce(output: impl IntoWriteRef<u32>) {
+///     output.into_write_ref().write_dyn(42);
+/// }
+/// fn main() {
+///     let mut a = 0;
+///     produce(&mut a);
+///     produce(Discard::new());
+///     assert_eq!(a, 42);
+/// }
+/// ```
+pub trait IntoWriteRef<T> {
+    /// The sink this converts into.
+    type Target: DynWriteRef<T>;
+
+    /// Convert this into a sink.
+    fn into_write_ref(self) -> Self::Target;
+}
+
+impl<'a, T: 'a> IntoWriteRef<T> for &'a mut T {
+    type Target = WriteRef<'a, T>;
+
+    fn into_write_ref(self) -> WriteRef<'a, T> {
+        WriteRef(self)
+    }
+}
+
+impl<'a, T: 'a> IntoWriteRef<T> for &'a Cell<T> {
+    type Target = CellWrite<'a, T>;
+
+    fn into_write_ref(self) -> CellWrite<'a, T> {
+        CellWrite(self)
+    }
+}
+
+impl<T: Clone, A: DynWriteRef<T>, B: DynWriteRef<T>> IntoWriteRef<T> for Tee<A, B> {
+    type Target = Self;
+
+    fn into_write_ref(self) -> Self {
+        self
+    }
+}
+
+impl<'a, T: 'a> IntoWriteRef<T> for WriteOnce<'a, T> {
+    type Target = Self;
+
+    fn into_write_ref(self) -> Self {
+        self
+    }
+}
+
+impl<T, W: DynWriteRef<T>> IntoWriteRef<T> for CountingWrite<W> {
+    type Target = Self;
+
+    fn into_write_ref(self) -> Self {
+        self
+    }
+}
+
+impl<'a, T: 'a, W: DynWriteRef<T>> IntoWriteRef<T> for Chain<'a, T, W> {
+    type Target = Self;
+
+    fn into_write_ref(self) -> Self {
+        self
+    }
+}
+
+/// A write-only buffer that keeps track of which of its elements have been
+/// written to.
+///
+/// This catches fill routines that skip elements by accident. It is only
+/// available with the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::{Tracked, WriteSlice};
+/// # fn main() {
+/// let mut buf = [0; 3];
+/// let mut slice = Tracked::from(WriteSlice::from(&mut buf));
+/// slice.write(0, 1);
+/// slice.write(2, 1);
+/// assert_eq!(slice.first_unwritten(), Some(1));
+/// slice.write(1, 1);
+/// slice.assert_fully_written();
+/// # }
+/// ```
+///
+/// ```should_panic
+/// # extern crate write_ref;
+/// # use write_ref::{Tracked, WriteSlice};
+/// # fn main() {
+/// let mut buf = [0; 3];
+/// let mut slice = Tracked::from(WriteSlice::from(&mut buf));
+/// slice.write(0, 1);
+/// slice.assert_fully_written(); // panics: elements [1, 2] were never written
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub struct Tracked<W> {
+    inner: W,
+    len: usize,
+    written: Vec<u64>,
+}
+
+#[cfg(feature = "alloc")]
+impl<W> Tracked<W> {
+    fn with_len(inner: W, len: usize) -> Self {
+        Tracked {
+            inner,
+            len,
+            written: alloc::vec![0; len.div_ceil(64)],
+        }
+    }
+
+    fn mark(&mut self, idx: usize) {
+        self.written[idx / 64] |= 1 << (idx % 64);
+    }
+
+    fn is_written(&self, idx: usize) -> bool {
+        self.written[idx / 64] & (1 << (idx % 64)) != 0
+    }
+
+    /// Returns the number of elements in this buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this buffer has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the index of the first element that hasn't been written to,
+    /// or `None` if every element has been.
+    pub fn first_unwritten(&self) -> Option<usize> {
+        (0..self.len).find(|&idx| !self.is_written(idx))
+    }
+
+    /// Assert that every element has been written to.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a list of the missing indices if any element hasn't been
+    /// written to.
+    pub fn assert_fully_written(&self) {
+        let missing: Vec<usize> = (0..self.len).filter(|&idx| !self.is_written(idx)).collect();
+        assert!(
+            missing.is_empty(),
+            "elements {:?} were never written",
+            missing
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> Tracked<WriteSlice<'a, T>> {
+    /// Write a value to an element of this buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn write(&mut self, idx: usize, val: T) {
+        self.inner.write(idx, val);
+        self.mark(idx);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> From<WriteSlice<'a, T>> for Tracked<WriteSlice<'a, T>> {
+    fn from(inner: WriteSlice<'a, T>) -> Self {
+        let len = inner.len();
+        Tracked::with_len(inner, len)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> Tracked<UninitWriteSlice<'a, T>> {
+    /// Initialize an element of this buffer with a value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn write(&mut self, idx: usize, val: T) {
+        self.inner.write(idx, val);
+        self.mark(idx);
+    }
+
+    /// Convert this buffer into a slice of initialized elements if every
+    /// element has been written to, or return the index of the first one
+    /// that hasn't.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use std::mem::MaybeUninit;
+    /// # use write_ref::{Tracked, UninitWriteSlice};
+    /// # fn main() {
+    /// let mut buf = [MaybeUninit::<u8>::uninit(); 3];
+    /// let mut slice = Tracked::from(UninitWriteSlice::from(&mut buf));
+    /// slice.write(0, 1);
+    /// slice.write(2, 3);
+    /// assert_eq!(slice.try_assume_init().err(), Some(1));
+    ///
+    /// let mut slice = Tracked::from(UninitWriteSlice::from(&mut buf));
+    /// for i in 0..slice.len() {
+    ///     slice.write(i, i as u8 + 1);
+    /// }
+    /// assert_eq!(slice.try_assume_init(), Ok(&mut [1, 2, 3][..]));
+    /// # }
+    /// ```
+    pub fn try_assume_init(self) -> Result<&'a mut [T], usize> {
+        match self.first_unwritten() {
+            Some(idx) => Err(idx),
+            // SAFETY: every element has been written to.
+            None => Ok(unsafe { self.inner.assume_init() }),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> From<UninitWriteSlice<'a, T>> for Tracked<UninitWriteSlice<'a, T>> {
+    fn from(inner: UninitWriteSlice<'a, T>) -> Self {
+        let len = inner.len();
+        Tracked::with_len(inner, len)
+    }
+}
+
+/// A write-only reference that calls a closure with each value before
+/// writing it.
+///
+/// This is returned by [`WriteRef::inspect`](struct.WriteRef.html#method.inspect).
+pub struct Inspect<'a, T: 'a, F> {
+    inner: WriteRef<'a, T>,
+    f: F,
+}
+
+impl<'a, T: 'a, F: FnMut(&T)> Inspect<'a, T, F> {
+    /// Pass a value to the closure, then write it to the underlying
+    /// reference.
+    pub fn write(&mut self, val: T) {
+        (self.f)(&val);
+        self.inner.write(val);
+    }
+}
+
+infallible_sink! {
+    ['a, T: 'a, F: FnMut(&T)] Inspect<'a, T, F> => T;
+}
+
+/// A write-only buffer that calls a closure with each index and value before
+/// writing it.
+///
+/// This is returned by [`WriteSlice::inspect_elem`](struct.WriteSlice.html#method.inspect_elem).
+pub struct InspectSlice<'a, T: 'a, F> {
+    inner: WriteSlice<'a, T>,
+    f: F,
+}
+
+impl<'a, T: 'a, F: FnMut(usize, &T)> InspectSlice<'a, T, F> {
+    /// Pass an index and value to the closure, then write the value to that
+    /// element of the underlying buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds. The closure isn't called in this
+    /// case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use std::panic::{self, AssertUnwindSafe};
+    /// # use write_ref::WriteSlice;
+    /// # fn main() {
+    /// let mut buf = [0; 2];
+    /// let mut seen = Vec::new();
+    /// let result = panic::catch_unwind(AssertUnwindSafe(|| {
+    ///     let mut slice = WriteSlice::from(&mut buf).inspect_elem(|i, _: &i32| seen.push(i));
+    ///     slice.write(2, 5);
+    /// }));
+    /// assert!(result.is_err());
+    /// assert!(seen.is_empty());
+    /// # }
+    /// ```
+    pub fn write(&mut self, idx: usize, val: T) {
+        check_index(idx, self.inner.len());
+        (self.f)(idx, &val);
+        self.inner.write(idx, val);
+    }
+
+    /// Returns the number of elements in this buffer.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if this buffer has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<'a, T: Unpin + 'a> IntoWriteRef<T> for Pin<&'a mut T> {
+    type Target = WriteRef<'a, T>;
+
+    fn into_write_ref(self) -> WriteRef<'a, T> {
+        WriteRef::from(self)
+    }
+}
+
+/// A write-only sink that appends every value written to it to a `Vec`.
+///
+/// Unlike a `WriteRef`, where each write replaces the previous value, this
+/// collects all of them. It is only available with the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::VecSink;
+/// # fn main() {
+/// let mut values = Vec::new();
+/// {
+///     let mut sink = VecSink::new(&mut values);
+///     for i in 0..3 {
+///         sink.write(i);
+///     }
+/// }
+/// assert_eq!(values, [0, 1, 2]);
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub struct VecSink<'a, T: 'a>(&'a mut Vec<T>);
+
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> VecSink<'a, T> {
+    /// Create a sink that appends to a `Vec`.
+    pub fn new(inner: &'a mut Vec<T>) -> Self {
+        VecSink(inner)
+    }
+
+    /// Append a value to the `Vec`.
+    pub fn write(&mut self, val: T) {
+        self.0.push(val);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> From<&'a mut Vec<T>> for VecSink<'a, T> {
+    fn from(inner: &'a mut Vec<T>) -> Self {
+        VecSink(inner)
+    }
+}
+
+infallible_sink! {
+    #[cfg(feature = "alloc")]
+    ['a, T: 'a] VecSink<'a, T> => T;
+}
+
+/// A write-only reference that only stores values greater than its current
+/// value.
+///
+/// A value that can't be compared with the current value, such as `NaN`,
+/// doesn't update it.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::MaxWrite;
+/// # fn main() {
+/// let mut max = 0;
+/// {
+///     let mut max_ref = MaxWrite::new(&mut max);
+///     for &v in &[3, 1, 4, 1, 5, 2] {
+///         max_ref.write(v);
+///     }
+/// }
+/// assert_eq!(max, 5);
+///
+/// let mut max = 0.0;
+/// MaxWrite::new(&mut max).write(std::f64::NAN);
+/// assert_eq!(max, 0.0);
+/// # }
+/// ```
+pub struct MaxWrite<'a, T: PartialOrd + 'a>(&'a mut T);
+
+impl<'a, T: PartialOrd + 'a> MaxWrite<'a, T> {
+    /// Create a reference that keeps the largest value written to it.
+    pub fn new(inner: &'a mut T) -> Self {
+        MaxWrite(inner)
+    }
+
+    /// Write a value if it is greater than the current value.
+    pub fn write(&mut self, val: T) {
+        if val > *self.0 {
+            *self.0 = val;
+        }
+    }
+}
+
+impl<'a, T: PartialOrd + 'a> From<&'a mut T> for MaxWrite<'a, T> {
+    fn from(inner: &'a mut T) -> Self {
+        MaxWrite(inner)
+    }
+}
+
+infallible_sink! {
+    ['a, T: PartialOrd + 'a] MaxWrite<'a, T> => T;
+}
+
+/// A write-only reference that only stores values less than its current
+/// value.
+///
+/// A value that can't be compared with the current value, such as `NaN`,
+/// doesn't update it.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::MinWrite;
+/// # fn main() {
+/// let mut min = 10;
+/// {
+///     let mut min_ref = MinWrite::new(&mut min);
+///     for &v in &[9, 7, 8, 3, 6] {
+///         min_ref.write(v);
+///     }
+/// }
+/// assert_eq!(min, 3);
+///
+/// let mut min = 10;
+/// {
+///     let mut min_ref = MinWrite::new(&mut min);
+///     for v in 11..14 {
+///         min_ref.write(v);
+///     }
+/// }
+/// assert_eq!(min, 10);
+/// # }
+/// ```
+pub struct MinWrite<'a, T: PartialOrd + 'a>(&'a mut T);
+
+impl<'a, T: PartialOrd + 'a> MinWrite<'a, T> {
+    /// Create a reference that keeps the smallest value written to it.
+    pub fn new(inner: &'a mut T) -> Self {
+        MinWrite(inner)
+    }
+
+    /// Write a value if it is less than the current value.
+    pub fn write(&mut self, val: T) {
+        if val < *self.0 {
+            *self.0 = val;
+        }
+    }
+}
+
+impl<'a, T: PartialOrd + 'a> From<&'a mut T> for MinWrite<'a, T> {
+    fn from(inner: &'a mut T) -> Self {
+        MinWrite(inner)
+    }
+}
+
+infallible_sink! {
+    ['a, T: PartialOrd + 'a] MinWrite<'a, T> => T;
+}
+
+/// A write-only reference that clamps values into a range before writing
+/// them.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::ClampWrite;
+/// # fn main() {
+/// let mut a = 0;
+/// ClampWrite::new(&mut a, 1, 10).write(-5);
+/// assert_eq!(a, 1);
+/// ClampWrite::new(&mut a, 1, 10).write(50);
+/// assert_eq!(a, 10);
+/// ClampWrite::new(&mut a, 1, 10).write(7);
+/// assert_eq!(a, 7);
+/// # }
+/// ```
+pub struct ClampWrite<'a, T: Ord + 'a> {
+    dst: &'a mut T,
+    min: T,
+    max: T,
+}
+
+impl<'a, T: Ord + 'a> ClampWrite<'a, T> {
+    /// Create a reference that clamps values into `[min, max]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max`.
+    pub fn new(dst: &'a mut T, min: T, max: T) -> Self {
+        assert!(min <= max, "ClampWrite requires min <= max");
+        ClampWrite { dst, min, max }
+    }
+
+    /// Write a value, clamped into the range.
+    pub fn write(&mut self, val: T)
+    where
+        T: Clone,
+    {
+        *self.dst = if val < self.min {
+            self.min.clone()
+        } else if val > self.max {
+            self.max.clone()
+        } else {
+            val
+        };
+    }
+}
+
+infallible_sink! {
+    ['a, T: Ord + Clone + 'a] ClampWrite<'a, T> => T;
+}
+
+/// A write-only sink that checks values with a predicate before writing them
+/// to another sink.
+///
+/// Writing a rejected value with `write` panics, while `try_write` returns
+/// it in `ValidateError::Rejected` instead. `try_write` also returns the
+/// underlying sink's errors, in `ValidateError::Inner`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::{SeqWrite, TryWriteRef, ValidateError, ValidateWrite};
+/// # fn main() {
+/// let mut a = 0;
+/// {
+///     let mut a_ref = ValidateWrite::new(&mut a, |v: &i32| *v >= 0);
+///     a_ref.write(3);
+///     assert_eq!(a_ref.try_write(-1), Err(ValidateError::Rejected(-1)));
+/// }
+/// assert_eq!(a, 3);
+///
+/// let mut v = [""; 2];
+/// {
+///     let mut sink = ValidateWrite::new(SeqWrite::new(&mut v), |s: &&str| !s.is_empty());
+///     assert_eq!(sink.try_write("a"), Ok(()));
+///     assert_eq!(sink.try_write(""), Err(ValidateError::Rejected("")));
+///     assert_eq!(sink.try_write("b"), Ok(()));
+///     assert_eq!(sink.try_write("c"), Err(ValidateError::Inner("c")));
+/// }
+/// assert_eq!(v, ["a", "b"]);
+/// # }
+/// ```
+///
+/// ```should_panic
+/// # extern crate write_ref;
+/// # use write_ref::ValidateWrite;
+/// # fn main() {
+/// let mut a = 0;
+/// ValidateWrite::new(&mut a, |v: &i32| *v >= 0).write(-1);
+/// # }
+/// ```
+pub struct ValidateWrite<W, F> {
+    inner: W,
+    f: F,
+}
+
+impl<W, F> ValidateWrite<W, F> {
+    /// Create a sink that only accepts values for which `f` returns `true`.
+    pub fn new<T>(inner: impl IntoWriteRef<T, Target = W>, f: F) -> Self
+    where
+        F: FnMut(&T) -> bool,
+    {
+        ValidateWrite {
+            inner: inner.into_write_ref(),
+            f,
+        }
+    }
+
+    /// Write a value to the underlying sink.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is rejected by the predicate.
+    pub fn write<T>(&mut self, val: T)
+    where
+        W: DynWriteRef<T>,
+        F: FnMut(&T) -> bool,
+    {
+        assert!((self.f)(&val), "ValidateWrite rejected a value");
+        self.inner.write_dyn(val);
+    }
+}
+
+impl<T, W: DynWriteRef<T>, F: FnMut(&T) -> bool> DynWriteRef<T> for ValidateWrite<W, F> {
+    fn write_dyn(&mut self, value: T) {
+        self.write(value);
+    }
+}
+
+/// Fails if the value is rejected by the predicate or the underlying sink
+/// fails. Rejected values aren't passed to the underlying sink.
+impl<T, W: TryWriteRef<T>, F: FnMut(&T) -> bool> TryWriteRef<T> for ValidateWrite<W, F> {
+    type Error = ValidateError<T, W::Error>;
+
+    fn try_write(&mut self, value: T) -> Result<(), ValidateError<T, W::Error>> {
+        if !(self.f)(&value) {
+            return Err(ValidateError::Rejected(value));
+        }
+        self.inner.try_write(value).map_err(ValidateError::Inner)
+    }
+}
+
+impl<T, W: DynWriteRef<T>, F: FnMut(&T) -> bool> IntoWriteRef<T> for ValidateWrite<W, F> {
+    type Target = Self;
+
+    fn into_write_ref(self) -> Self {
+        self
+    }
+}
+
+/// The error returned by `ValidateWrite::try_write`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidateError<T, E> {
+    /// The predicate rejected the value, which is returned.
+    Rejected(T),
+    /// The underlying sink failed.
+    Inner(E),
+}
+
+impl<T, E: fmt::Display> fmt::Display for ValidateError<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ValidateError::Rejected(_) => f.write_str("value was rejected by the predicate"),
+            ValidateError::Inner(ref e) => write!(f, "underlying sink failed: {}", e),
+        }
+    }
+}
+
+impl<T: fmt::Debug, E: Error> Error for ValidateError<T, E> {}
+
+/// Conversion of a collection's existing elements into a `WriteSlice`.
+///
+/// The view covers the elements that are already there, so writing through
+/// it never changes the collection's length.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::AsWriteSlice;
+/// # fn main() {
+/// let mut a = [0; 2];
+/// a.as_write_slice().fill(1);
+/// assert_eq!(a, [1, 1]);
+///
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// let mut v = vec![0; 3];
+/// v.as_write_slice().write(1, 5);
+/// assert_eq!(v, [0, 5, 0]);
+/// # }
+/// # }
+/// ```
+pub trait AsWriteSlice<T> {
+    /// Returns a write-only view of the elements.
+    fn as_write_slice(&mut self) -> WriteSlice<'_, T>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> AsWriteSlice<T> for Vec<T> {
+    fn as_write_slice(&mut self) -> WriteSlice<'_, T> {
+        WriteSlice(self)
+    }
+}
+
+impl<T, const N: usize> AsWriteSlice<T> for [T; N] {
+    fn as_write_slice(&mut self) -> WriteSlice<'_, T> {
+        WriteSlice(self)
+    }
+}
+
+/// A write-only array whose indices are checked at compile time.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::ConstWriteSlice;
+/// # fn main() {
+/// let mut buf = [0; 3];
+/// {
+///     let mut slice = ConstWriteSlice::from(&mut buf);
+///     slice.write_at::<0>(1);
+///     slice.write_at::<2>(3);
+/// }
+/// assert_eq!(buf, [1, 0, 3]);
+/// # }
+/// ```
+///
+/// An index past the end is rejected when the program is compiled:
+///
+/// ```compile_fail
+/// # extern crate write_ref;
+/// # use write_ref::ConstWriteSlice;
+/// # fn main() {
+/// let mut buf = [0; 3];
+/// ConstWriteSlice::from(&mut buf).write_at::<3>(1);
+/// # }
+/// ```
+pub struct ConstWriteSlice<'a, T: 'a, const N: usize>(&'a mut [T; N]);
+
+impl<'a, T: 'a, const N: usize> ConstWriteSlice<'a, T, N> {
+    /// Write a value to the element at index `I`.
+    pub fn write_at<const I: usize>(&mut self, val: T) {
+        const { assert!(I < N, "index out of bounds") };
+        self.0[I] = val;
+    }
+
+    /// Returns the number of elements in this array.
+    pub fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if this array has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+}
+
+impl<'a, T: 'a, const N: usize> From<&'a mut [T; N]> for ConstWriteSlice<'a, T, N> {
+    fn from(inner: &'a mut [T; N]) -> Self {
+        ConstWriteSlice(inner)
+    }
+}
+
+/// A write-only reference that zeroizes the previous value before writing a
+/// new one.
+///
+/// This keeps secrets such as key material from lingering in memory after
+/// they are replaced. It is only available with the `zeroize` feature.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # extern crate zeroize;
+/// # use std::cell::RefCell;
+/// # use write_ref::ZeroizeWrite;
+/// # use zeroize::Zeroize;
+/// thread_local!(static ZEROIZED: RefCell<Vec<u8>> = RefCell::new(Vec::new()));
+/// struct Key(u8);
+/// impl Zeroize for Key {
+///     fn zeroize(&mut self) {
+///         ZEROIZED.with(|z| z.borrow_mut().push(self.0));
+///         self.0 = 0;
+///     }
+/// }
+/// fn main() {
+///     let mut key = Key(1);
+///     ZeroizeWrite::new(&mut key).write(Key(2));
+///     assert_eq!(key.0, 2);
+///     ZEROIZED.with(|z| assert_eq!(*z.borrow(), [1]));
+/// }
+/// ```
+#[cfg(feature = "zeroize")]
+pub struct ZeroizeWrite<'a, T: Zeroize + 'a>(&'a mut T);
+
+#[cfg(feature = "zeroize")]
+impl<'a, T: Zeroize + 'a> ZeroizeWrite<'a, T> {
+    /// Create a reference that zeroizes its previous value on every write.
+    pub fn new(inner: &'a mut T) -> Self {
+        ZeroizeWrite(inner)
+    }
+
+    /// Zeroize the current value, then write a new one.
+    pub fn write(&mut self, val: T) {
+        self.0.zeroize();
+        *self.0 = val;
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<'a, T: Zeroize + 'a> From<&'a mut T> for ZeroizeWrite<'a, T> {
+    fn from(inner: &'a mut T) -> Self {
+        ZeroizeWrite(inner)
+    }
+}
+
+infallible_sink! {
+    #[cfg(feature = "zeroize")]
+    ['a, T: Zeroize + 'a] ZeroizeWrite<'a, T> => T;
+}
+
+/// Zeroize every element of a write-only buffer.
+///
+/// It is only available with the `zeroize` feature.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # fn main() {
+/// let mut secret = [7u8; 4];
+/// write_ref::zeroize_slice(&mut secret);
+/// assert_eq!(secret, [0; 4]);
+/// # }
+/// ```
+#[cfg(feature = "zeroize")]
+pub fn zeroize_slice<'a, T: Zeroize + 'a>(slice: impl Into<WriteSlice<'a, T>>) {
+    for elem in slice.into().0.iter_mut() {
+        elem.zeroize();
+    }
+}
+
+/// A write-only reference to an atomic value that writes with `store`.
+///
+/// This lets code that writes through a shared reference, such as a single
+/// writer publishing to other threads, use a write-only sink. Writes use
+/// `Ordering::Relaxed` unless another ordering is given to `with_ordering`.
+/// It can be created for `AtomicBool` and the integer atomics.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// # use write_ref::AtomicWrite;
+/// # fn main() {
+/// let counter = AtomicUsize::new(0);
+/// let shared = &counter;
+/// AtomicWrite::new(shared).write(3);
+/// assert_eq!(counter.load(Ordering::Relaxed), 3);
+///
+/// let mut sink = AtomicWrite::with_ordering(shared, Ordering::SeqCst);
+/// assert_eq!(sink.ordering(), Ordering::SeqCst);
+/// sink.write(4);
+/// assert_eq!(counter.load(Ordering::SeqCst), 4);
+/// # }
+/// ```
+///
+/// ```should_panic
+/// # extern crate write_ref;
+/// # use std::sync::atomic::{AtomicBool, Ordering};
+/// # use write_ref::AtomicWrite;
+/// # fn main() {
+/// let flag = AtomicBool::new(false);
+/// AtomicWrite::with_ordering(&flag, Ordering::Acquire); // panics
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # extern crate write_ref;
+/// # use write_ref::AtomicWrite;
+/// # fn main() {
+/// let s = String::new();
+/// AtomicWrite::new(&s); // error: `String` isn't an atomic type
+/// # }
+/// ```
+pub struct AtomicWrite<'a, A: 'a> {
+    atomic: &'a A,
+    ordering: Ordering,
+}
+
+mod sealed {
+    /// Implemented for the atomic types that `AtomicWrite` supports.
+    pub trait Atomic {}
+}
+
+impl<'a, A: sealed::Atomic + 'a> AtomicWrite<'a, A> {
+    /// Create a reference that writes with `Ordering::Relaxed`.
+    pub fn new(atomic: &'a A) -> Self {
+        AtomicWrite::with_ordering(atomic, Ordering::Relaxed)
+    }
+
+    /// Create a reference that writes with the given ordering.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ordering` is `Acquire` or `AcqRel`, since those aren't
+    /// valid for a store.
+    pub fn with_ordering(atomic: &'a A, ordering: Ordering) -> Self {
+        if let Ordering::Acquire | Ordering::AcqRel = ordering {
+            panic!("{:?} is not a valid ordering for a store", ordering);
+        }
+        AtomicWrite { atomic, ordering }
+    }
+
+    /// Returns the ordering used for writes.
+    pub fn ordering(&self) -> Ordering {
+        self.ordering
+    }
+}
+
+macro_rules! atomic_write {
+    ($($width:literal, $atomic:ident, $t:ty;)*) => {$(
+        #[cfg(target_has_atomic = $width)]
+        impl sealed::Atomic for atomic::$atomic {}
+
+        #[cfg(target_has_atomic = $width)]
+        impl<'a> AtomicWrite<'a, atomic::$atomic> {
+            /// Store a value in the atomic.
+            pub fn write(&mut self, val: $t) {
+                self.atomic.store(val, self.ordering);
+            }
+        }
+
+        infallible_sink! {
+            #[cfg(target_has_atomic = $width)]
+            ['a] AtomicWrite<'a, atomic::$atomic> => $t;
+        }
+    )*};
+}
+
+atomic_write! {
+    "8", AtomicBool, bool;
+    "8", AtomicI8, i8;
+    "8", AtomicU8, u8;
+    "16", AtomicI16, i16;
+    "16", AtomicU16, u16;
+    "32", AtomicI32, i32;
+    "32", AtomicU32, u32;
+    "64", AtomicI64, i64;
+    "64", AtomicU64, u64;
+    "ptr", AtomicIsize, isize;
+    "ptr", AtomicUsize, usize;
+}
+
+/// A write-only sink of pairs whose components are written to two
+/// references.
+///
+/// This is returned by [`join`](fn.join.html).
+pub struct Join<'a, A: 'a, B: 'a> {
+    a: WriteRef<'a, A>,
+    b: WriteRef<'a, B>,
+}
+
+impl<'a, A: 'a, B: 'a> Join<'a, A, B> {
+    /// Write the first component of a pair to the first reference, and the
+    /// second component to the second reference.
+    pub fn write(&mut self, (x, y): (A, B)) {
+        self.a.write(x);
+        self.b.write(y);
+    }
+}
+
+/// Create a write-only sink of pairs that writes the first component of each
+/// pair to `a` and the second to `b`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # fn main() {
+/// let mut quotient = 0;
+/// let mut remainder = 0;
+/// write_ref::join(&mut quotient, &mut remainder).write((17 / 5, 17 % 5));
+/// assert_eq!((quotient, remainder), (3, 2));
+/// # }
+/// ```
+pub fn join<'a, A: 'a, B: 'a>(
+    a: impl Into<WriteRef<'a, A>>,
+    b: impl Into<WriteRef<'a, B>>,
+) -> Join<'a, A, B> {
+    Join {
+        a: a.into(),
+        b: b.into(),
+    }
+}
+
+infallible_sink! {
+    ['a, A: 'a, B: 'a] Join<'a, A, B> => (A, B);
+}
+
+/// A write-only sink that logs each value before writing it to another
+/// sink.
+///
+/// This is returned by [`log`](fn.log.html) and
+/// [`log_stderr`](fn.log_stderr.html).
+pub struct LoggingWrite<W, F> {
+    inner: W,
+    f: F,
+}
+
+impl<W, F> LoggingWrite<W, F> {
+    /// Pass a value to the logging function, then write it to the underlying
+    /// sink.
+    pub fn write<T>(&mut self, val: T)
+    where
+        W: DynWriteRef<T>,
+        F: FnMut(&T),
+    {
+        (self.f)(&val);
+        self.inner.write_dyn(val);
+    }
+}
+
+/// Create a write-only sink that passes each value to `f` before writing it
+/// to `inner`.
+///
+/// This works like `WriteRef::inspect`, but for any sink, and is meant for
+/// diagnostics.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::{SeqWrite, TryWriteRef};
+/// # fn main() {
+/// let mut a = 0;
+/// let mut log = Vec::new();
+/// {
+///     let mut a_ref = write_ref::log(&mut a, |v: &i32| log.push(format!("wrote {}", v)));
+///     a_ref.write(1);
+///     a_ref.write(2);
+/// }
+/// assert_eq!(log, ["wrote 1", "wrote 2"]);
+/// assert_eq!(a, 2);
+///
+/// let mut v = [' '; 2];
+/// let mut count = 0;
+/// {
+///     let mut sink = write_ref::log(SeqWrite::new(&mut v), |_: &char| count += 1);
+///     sink.write('a');
+///     sink.write('b');
+///     assert_eq!(sink.try_write('c'), Err('c'));
+/// }
+/// assert_eq!((v, count), (['a', 'b'], 3));
+/// # }
+/// ```
+pub fn log<T, F: FnMut(&T), W: IntoWriteRef<T>>(inner: W, f: F) -> LoggingWrite<W::Target, F> {
+    LoggingWrite {
+        inner: inner.into_write_ref(),
+        f,
+    }
+}
+
+/// Create a write-only sink that prints each value to standard error with
+/// `eprintln!` before writing it to `inner`.
+///
+/// It is only available with the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # fn main() {
+/// let mut a = 0;
+/// write_ref::log_stderr(&mut a).write(5); // prints "5"
+/// assert_eq!(a, 5);
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn log_stderr<T: fmt::Debug, W: IntoWriteRef<T>>(inner: W) -> LoggingWrite<W::Target, fn(&T)> {
+    fn print<T: fmt::Debug>(val: &T) {
+        std::eprintln!("{:?}", val);
+    }
+    log(inner, print::<T>)
+}
+
+impl<T, W: DynWriteRef<T>, F: FnMut(&T)> DynWriteRef<T> for LoggingWrite<W, F> {
+    fn write_dyn(&mut self, value: T) {
+        self.write(value);
+    }
+}
+
+/// Fails if the underlying sink fails. The value is logged either way.
+impl<T, W: TryWriteRef<T>, F: FnMut(&T)> TryWriteRef<T> for LoggingWrite<W, F> {
+    type Error = W::Error;
+
+    fn try_write(&mut self, value: T) -> Result<(), W::Error> {
+        (self.f)(&value);
+        self.inner.try_write(value)
+    }
+}
+
+impl<T, W: DynWriteRef<T>, F: FnMut(&T)> IntoWriteRef<T> for LoggingWrite<W, F> {
+    type Target = Self;
+
+    fn into_write_ref(self) -> Self {
+        self
+    }
+}
+
+/// A borrowed write-only sink.
+///
+/// This is returned by [`DynWriteRef::by_ref`](trait.DynWriteRef.html#method.by_ref).
+pub struct ByRef<'a, W: 'a>(&'a mut W);
+
+impl<'a, T, W: DynWriteRef<T> + 'a> DynWriteRef<T> for ByRef<'a, W> {
+    fn write_dyn(&mut self, value: T) {
+        self.0.write_dyn(value);
+    }
+}
+
+impl<'a, T, W: TryWriteRef<T> + 'a> TryWriteRef<T> for ByRef<'a, W> {
+    type Error = W::Error;
+
+    fn try_write(&mut self, value: T) -> Result<(), W::Error> {
+        self.0.try_write(value)
+    }
+}
+
+impl<'a, T, W: DynWriteRef<T> + 'a> IntoWriteRef<T> for ByRef<'a, W> {
+    type Target = Self;
+
+    fn into_write_ref(self) -> Self {
+        self
+    }
+}
+
+/// Writing a tuple of values to a tuple of mutable references at once.
+///
+/// This is implemented for tuples of 2 to 8 mutable references.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::WriteAll;
+/// # fn main() {
+/// let (mut a, mut b, mut c, mut d) = (0, ' ', "", 0.0);
+/// (&mut a, &mut b).write_all((1, 'x'));
+/// assert_eq!((a, b), (1, 'x'));
+/// (&mut a, &mut b, &mut c).write_all((2, 'y', "z"));
+/// assert_eq!((a, b, c), (2, 'y', "z"));
+/// (&mut a, &mut b, &mut c, &mut d).write_all((3, 'w', "v", 1.5));
+/// assert_eq!((a, b, c, d), (3, 'w', "v", 1.5));
+/// # }
+/// ```
+pub trait WriteAll {
+    /// The tuple of values that can be written.
+    type Values;
+
+    /// Write each value to its corresponding reference.
+    fn write_all(self, values: Self::Values);
+}
+
+macro_rules! write_all {
+    ($($t:ident $r:ident $v:ident),*) => {
+        impl<'a, $($t: 'a),*> WriteAll for ($(&'a mut $t,)*) {
+            type Values = ($($t,)*);
+
+            fn write_all(self, values: Self::Values) {
+                let ($($r,)*) = self;
+                let ($($v,)*) = values;
+                $(*$r = $v;)*
+            }
+        }
+    };
+}
+
+write_all!(A ra va, B rb vb);
+write_all!(A ra va, B rb vb, C rc vc);
+write_all!(A ra va, B rb vb, C rc vc, D rd vd);
+write_all!(A ra va, B rb vb, C rc vc, D rd vd, E re ve);
+write_all!(A ra va, B rb vb, C rc vc, D rd vd, E re ve, F rf vf);
+write_all!(A ra va, B rb vb, C rc vc, D rd vd, E re ve, F rf vf, G rg vg);
+write_all!(A ra va, B rb vb, C rc vc, D rd vd, E re ve, F rf vf, G rg vg, H rh vh);
+
+/// A write-only buffer that alternates between the elements of two slices.
+///
+/// This is returned by [`interleave2`](fn.interleave2.html).
+pub struct Interleave<'a, T: 'a> {
+    a: WriteSlice<'a, T>,
+    b: WriteSlice<'a, T>,
+}
+
+impl<'a, T: 'a> Interleave<'a, T> {
+    /// Write a value to element `idx / 2` of the first slice if `idx` is
+    /// even, or of the second slice if `idx` is odd.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn write(&mut self, idx: usize, val: T) {
+        if idx % 2 == 0 {
+            self.a.write(idx / 2, val);
+        } else {
+            self.b.write(idx / 2, val);
+        }
+    }
+
+    /// Returns the combined number of elements in both slices.
+    pub fn len(&self) -> usize {
+        self.a.len() + self.b.len()
+    }
+
+    /// Returns `true` if both slices have a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.a.is_empty()
+    }
+}
+
+/// Create a write-only buffer whose even indices are written to `a` and
+/// whose odd indices are written to `b`.
+///
+/// Index `2 * i` refers to `a[i]`, and index `2 * i + 1` refers to `b[i]`.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # fn main() {
+/// let mut left = [0; 3];
+/// let mut right = [0; 3];
+/// {
+///     let mut stereo = write_ref::interleave2(&mut left, &mut right);
+///     for i in 0..stereo.len() {
+///         stereo.write(i, i);
+///     }
+/// }
+/// assert_eq!(left, [0, 2, 4]);
+/// assert_eq!(right, [1, 3, 5]);
+/// # }
+/// ```
+pub fn interleave2<'a, T: 'a>(
+    a: impl Into<WriteSlice<'a, T>>,
+    b: impl Into<WriteSlice<'a, T>>,
+) -> Interleave<'a, T> {
+    let a = a.into();
+    let b = b.into();
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "interleave2 requires slices of equal length"
+    );
+    Interleave { a, b }
+}
+
+/// A `fmt::Write` implementation that writes bytes to a write-only buffer.
+///
+/// This allows using `write!` to format text into a fixed-size buffer, even
+/// without `std`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use std::fmt::Write;
+/// # use write_ref::{FmtSlice, WriteSlice};
+/// # fn main() {
+/// let mut buf = [0u8; 8];
+/// {
+///     let mut out = FmtSlice::new(WriteSlice::from(&mut buf));
+///     write!(out, "{}-{}", 12, 34).unwrap();
+///     assert_eq!(out.position(), 5);
+///     assert!(write!(out, "long").is_err());
+/// }
+/// assert_eq!(&buf[..5], b"12-34");
+/// # }
+/// ```
+pub struct FmtSlice<'a> {
+    inner: WriteSlice<'a, u8>,
+    pos: usize,
+}
+
+impl<'a> FmtSlice<'a> {
+    /// Create a formatter that writes to the start of a buffer.
+    pub fn new(inner: impl Into<WriteSlice<'a, u8>>) -> Self {
+        FmtSlice {
+            inner: inner.into(),
+            pos: 0,
+        }
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<'a> fmt::Write for FmtSlice<'a> {
+    /// Write a string's bytes after the previously written ones.
+    ///
+    /// If the string doesn't fit in the rest of the buffer, nothing is written
+    /// and `fmt::Error` is returned.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.pos + s.len();
+        if end > self.inner.len() {
+            return Err(fmt::Error);
+        }
+        self.inner.0[self.pos..end].copy_from_slice(s.as_bytes());
+        self.pos = end;
+        Ok(())
+    }
+}
+
+/// A write-only sink that writes each value to the next element of a buffer.
+///
+/// Its `TryWriteRef` implementation returns the value instead of panicking
+/// once the buffer is full.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::{SeqWrite, TryWriteRef};
+/// fn send_all<S: TryWriteRef<u8>>(sink: &mut S, values: &[u8]) -> Result<(), S::Error> {
+///     for &v in values {
+///         sink.try_write(v)?;
+///     }
+///     Ok(())
+/// }
+/// # fn main() {
+/// let mut buf = [0; 3];
+/// {
+///     let mut seq = SeqWrite::new(&mut buf);
+///     seq.write(1);
+///     assert_eq!((seq.position(), seq.remaining()), (1, 2));
+///     assert_eq!(send_all(&mut seq, &[2, 3, 4]), Err(4));
+/// }
+/// assert_eq!(buf, [1, 2, 3]);
+/// # }
+/// ```
+///
+/// ```should_panic
+/// # extern crate write_ref;
+/// # use write_ref::SeqWrite;
+/// # fn main() {
+/// let mut buf = [0; 1];
+/// let mut seq = SeqWrite::new(&mut buf);
+/// seq.write(1);
+/// seq.write(2); // panics
+/// # }
+/// ```
+pub struct SeqWrite<'a, T: 'a> {
+    inner: WriteSlice<'a, T>,
+    pos: usize,
+}
+
+impl<'a, T: 'a> SeqWrite<'a, T> {
+    /// Create a sink that writes to a buffer starting at its first element.
+    pub fn new(inner: impl Into<WriteSlice<'a, T>>) -> Self {
+        SeqWrite {
+            inner: inner.into(),
+            pos: 0,
+        }
+    }
+
+    /// Write a value to the next element of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is full.
+    pub fn write(&mut self, val: T) {
+        assert!(self.remaining() > 0, "SeqWrite buffer is full");
+        self.inner.write(self.pos, val);
+        self.pos += 1;
+    }
+
+    /// Returns the number of values written so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the number of values that can still be written.
+    pub fn remaining(&self) -> usize {
+        self.inner.len() - self.pos
+    }
+}
+
+impl<'a, T: 'a> DynWriteRef<T> for SeqWrite<'a, T> {
+    fn write_dyn(&mut self, value: T) {
+        self.write(value);
+    }
+}
+
+/// Fails if the buffer is full, returning the value.
+impl<'a, T: 'a> TryWriteRef<T> for SeqWrite<'a, T> {
+    type Error = T;
+
+    fn try_write(&mut self, value: T) -> Result<(), T> {
+        self.inner.try_write(self.pos, value)?;
+        self.pos += 1;
+        Ok(())
+    }
+}
+
+impl<'a, T: 'a> IntoWriteRef<T> for SeqWrite<'a, T> {
+    type Target = Self;
+
+    fn into_write_ref(self) -> Self {
+        self
+    }
+}
+
+/// A write-only reference to a `RefCell`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use std::cell::RefCell;
+/// # use std::rc::Rc;
+/// # use write_ref::RefCellWrite;
+/// # fn main() {
+/// let shared = Rc::new(RefCell::new(0));
+/// RefCellWrite::new(&shared).write(5);
+/// assert_eq!(*shared.borrow(), 5);
+/// # }
+/// ```
+///
+/// ```should_panic
+/// # extern crate write_ref;
+/// # use std::cell::RefCell;
+/// # use write_ref::RefCellWrite;
+/// # fn main() {
+/// let cell = RefCell::new(0);
+/// let _borrow = cell.borrow();
+/// RefCellWrite::new(&cell).write(5); // panics
+/// # }
+/// ```
+pub struct RefCellWrite<'a, T: 'a>(&'a RefCell<T>);
+
+impl<'a, T: 'a> RefCellWrite<'a, T> {
+    /// Create a write-only reference to a `RefCell`.
+    pub fn new(inner: &'a RefCell<T>) -> Self {
+        RefCellWrite(inner)
+    }
+
+    /// Write a value to the cell.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is currently borrowed.
+    pub fn write(&mut self, val: T) {
+        *self.0.borrow_mut() = val;
+    }
+}
+
+impl<'a, T: 'a> From<&'a RefCell<T>> for RefCellWrite<'a, T> {
+    fn from(inner: &'a RefCell<T>) -> Self {
+        RefCellWrite(inner)
+    }
+}
+
+impl<'a, T: 'a> DynWriteRef<T> for RefCellWrite<'a, T> {
+    fn write_dyn(&mut self, value: T) {
+        self.write(value);
+    }
+}
+
+/// Fails if the cell is currently borrowed, returning the value.
+impl<'a, T: 'a> TryWriteRef<T> for RefCellWrite<'a, T> {
+    type Error = T;
+
+    fn try_write(&mut self, value: T) -> Result<(), T> {
+        match self.0.try_borrow_mut() {
+            Ok(mut cell) => {
+                *cell = value;
+                Ok(())
+            }
+            Err(_) => Err(value),
+        }
+    }
+}
+
+impl<'a, T: 'a> IntoWriteRef<T> for RefCellWrite<'a, T> {
+    type Target = Self;
+
+    fn into_write_ref(self) -> Self {
+        self
+    }
+}
+
+/// A write-only view of a slice whose indices are remapped by a function.
+///
+/// This is returned by [`WriteSlice::map_index`](struct.WriteSlice.html#method.map_index).
+pub struct MapIndex<'a, T: 'a, F> {
+    slice: &'a mut [T],
+    f: F,
+    len: usize,
+}
+
+impl<'a, T: 'a, F: Fn(usize) -> usize> MapIndex<'a, T, F> {
+    /// Write a value to the element at `f(idx)` in the underlying slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds for this view, or if `f(idx)` is out
+    /// of bounds for the underlying slice.
+    pub fn write(&mut self, idx: usize, val: T) {
+        check_index(idx, self.len);
+        self.slice[(self.f)(idx)] = val;
+    }
+
+    /// Returns the number of elements in this view.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this view has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Conversion between primitive integer types that saturates at the bounds
+/// of the target type.
+///
+/// This is used by [`WriteRef::saturating_cast`](struct.WriteRef.html#method.saturating_cast).
+pub trait SaturatingFrom<U> {
+    /// Convert a value, returning the minimum or maximum of `Self` if it
+    /// doesn't fit.
+    fn saturating_from(val: U) -> Self;
+}
+
+macro_rules! saturating_from {
+    ($($t:ty)*) => {
+        saturating_from!(@each [$($t)*] $($t)*);
+    };
+    (@each $all:tt $($t:ty)*) => {
+        $(saturating_from!(@impl $t, $all);)*
+    };
+    (@impl $t:ty, [$($u:ty)*]) => {$(
+        impl SaturatingFrom<$u> for $t {
+            fn saturating_from(val: $u) -> $t {
+                match <$t>::try_from(val) {
+                    Ok(val) => val,
+                    Err(_) if val < <$u>::default() => <$t>::MIN,
+                    Err(_) => <$t>::MAX,
+                }
+            }
+        }
+    )*};
+}
+
+saturating_from!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+/// A write-only buffer over the existing elements of a `VecDeque`.
+///
+/// Indices are logical positions in the deque, as with `VecDeque::get_mut`,
+/// regardless of how its storage wraps around. Writing never pushes new
+/// elements. It is only available with the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use std::collections::VecDeque;
+/// # use write_ref::DequeWrite;
+/// # fn main() {
+/// let mut deque: VecDeque<_> = (0..4).collect();
+/// deque.rotate_left(3);
+/// {
+///     let mut slice = DequeWrite::new(&mut deque);
+///     for i in 0..slice.len() {
+///         slice.write(i, i * 10);
+///     }
+/// }
+/// assert_eq!(deque, [0, 10, 20, 30]);
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub struct DequeWrite<'a, T: 'a>(&'a mut VecDeque<T>);
+
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> DequeWrite<'a, T> {
+    /// Create a write-only buffer over a deque's elements.
+    pub fn new(inner: &'a mut VecDeque<T>) -> Self {
+        DequeWrite(inner)
+    }
+
+    /// Write a value to the element at a logical position in the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn write(&mut self, idx: usize, val: T) {
+        let len = self.0.len();
+        match self.0.get_mut(idx) {
+            Some(elem) => *elem = val,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                len, idx
+            ),
+        }
+    }
+
+    /// Returns the number of elements in the deque.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the deque is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> From<&'a mut VecDeque<T>> for DequeWrite<'a, T> {
+    fn from(inner: &'a mut VecDeque<T>) -> Self {
+        DequeWrite(inner)
+    }
+}
+
+/// Splitting a mutable reference to a small array into a tuple of write-only
+/// references to its elements.
+///
+/// This is implemented for mutable references to arrays of 2 to 4 elements.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::IntoWriteRefs;
+/// # fn main() {
+/// let mut pair = [0; 2];
+/// let (mut a, mut b) = (&mut pair).into_write_refs();
+/// b.write(2);
+/// a.write(1);
+/// assert_eq!(pair, [1, 2]);
+///
+/// let mut quad = [0; 4];
+/// let (_, mut b, _, mut d) = (&mut quad).into_write_refs();
+/// d.write(4);
+/// b.write(2);
+/// assert_eq!(quad, [0, 2, 0, 4]);
+/// # }
+/// ```
+pub trait IntoWriteRefs {
+    /// The tuple of write-only references.
+    type Refs;
+
+    /// Split into a write-only reference to each element.
+    fn into_write_refs(self) -> Self::Refs;
+}
+
+macro_rules! into_write_refs {
+    ($n:expr; $($r:ident),*) => {
+        impl<'a, T: 'a> IntoWriteRefs for &'a mut [T; $n] {
+            type Refs = ($(into_write_refs!(@ref $r T),)*);
+
+            fn into_write_refs(self) -> Self::Refs {
+                let [$($r,)*] = self;
+                ($(WriteRef($r),)*)
+            }
+        }
+    };
+    (@ref $r:ident $t:ident) => {
+        WriteRef<'a, $t>
+    };
+}
+
+into_write_refs!(2; a, b);
+into_write_refs!(3; a, b, c);
+into_write_refs!(4; a, b, c, d);
+
+/// A write-only sink that overwrites the elements of a `Vec` in order, then
+/// appends to it once it runs out of elements.
+///
+/// This reuses a `Vec`'s existing elements rather than clearing it first.
+/// Elements past the last written position are left as they were. It is
+/// only available with the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::GrowSink;
+/// # fn main() {
+/// let mut values = vec![0, 0];
+/// {
+///     let mut sink = GrowSink::new(&mut values);
+///     for i in 1..5 {
+///         sink.write(i);
+///     }
+///     assert_eq!(sink.position(), 4);
+/// }
+/// assert_eq!(values, [1, 2, 3, 4]);
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub struct GrowSink<'a, T: 'a> {
+    inner: &'a mut Vec<T>,
+    pos: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> GrowSink<'a, T> {
+    /// Create a sink that starts writing at the beginning of a `Vec`.
+    pub fn new(inner: &'a mut Vec<T>) -> Self {
+        GrowSink { inner, pos: 0 }
+    }
+
+    /// Overwrite the element at the current position, or append the value
+    /// if the position is past the end of the `Vec`.
+    pub fn write(&mut self, val: T) {
+        match self.inner.get_mut(self.pos) {
+            Some(elem) => *elem = val,
+            None => self.inner.push(val),
+        }
+        self.pos += 1;
+    }
+
+    /// Returns the number of values written so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> From<&'a mut Vec<T>> for GrowSink<'a, T> {
+    fn from(inner: &'a mut Vec<T>) -> Self {
+        GrowSink::new(inner)
+    }
+}
+
+infallible_sink! {
+    #[cfg(feature = "alloc")]
+    ['a, T: 'a] GrowSink<'a, T> => T;
+}
+
+/// A write-only view of a slice that ignores writes to some of its indices.
+///
+/// This is returned by [`WriteSlice::masked`](struct.WriteSlice.html#method.masked).
+pub struct Masked<'a, T: 'a, F> {
+    slice: &'a mut [T],
+    mask: F,
+}
+
+impl<'a, T: 'a, F: Fn(usize) -> bool> Masked<'a, T, F> {
+    /// Write a value to an element of the underlying slice if `mask(idx)`
+    /// returns `true`, or drop it otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds, even if it's masked out.
+    pub fn write(&mut self, idx: usize, val: T) {
+        let elem = &mut self.slice[idx];
+        if (self.mask)(idx) {
+            *elem = val;
+        }
+    }
+
+    /// Returns the number of elements in this view.
+    pub fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    /// Returns `true` if this view has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.slice.is_empty()
+    }
+}
+
+/// A write-only sink that passes every value written to it through
+/// `core::hint::black_box` before dropping it.
+///
+/// Unlike [`Discard`](struct.Discard.html), whose writes the compiler may
+/// optimize away along with the code producing the values, this keeps that
+/// work observable. It's intended as the output of code measured in
+/// benchmarks, such as with `criterion`:
+///
+/// ```ignore
+/// c.bench_function("produce", |b| {
+///     b.iter(|| produce(BlackBoxWrite::new()))
+/// });
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::{BlackBoxWrite, IntoWriteRef, DynWriteRef};
+/// fn produce(output: impl IntoWriteRef<u64>) {
+///     let mut output = output.into_write_ref();
+///     for i in 0..100 {
+///         output.write_dyn(i * i);
+///     }
+/// }
+/// # fn main() {
+/// produce(BlackBoxWrite::new());
+/// # }
+/// ```
+pub struct BlackBoxWrite<T>(PhantomData<fn(T)>);
+
+impl<T> BlackBoxWrite<T> {
+    /// Create a new `BlackBoxWrite`.
+    pub const fn new() -> Self {
+        BlackBoxWrite(PhantomData)
+    }
+
+    /// Pass a value through `core::hint::black_box`, then drop it.
+    pub fn write(&mut self, val: T) {
+        drop(core::hint::black_box(val));
+    }
+}
+
+impl<T> Clone for BlackBoxWrite<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for BlackBoxWrite<T> {}
+
+impl<T> Default for BlackBoxWrite<T> {
+    fn default() -> Self {
+        BlackBoxWrite::new()
+    }
+}
+
+infallible_sink! {
+    [T] BlackBoxWrite<T> => T;
+}
+
+/// A write-only sink that calls a function pointer with every value written
+/// to it.
+///
+/// Unlike [`from_fn`](fn.from_fn.html), this can't capture any state, but it
+/// is always `Copy` and its type doesn't depend on the function.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use std::sync::atomic::{AtomicU32, Ordering};
+/// # use write_ref::FnPtrWrite;
+/// static LAST: AtomicU32 = AtomicU32::new(0);
+///
+/// fn store(val: u32) {
+///     LAST.store(val, Ordering::Relaxed);
+/// }
+///
+/// # fn main() {
+/// let mut sink = FnPtrWrite::new(store);
+/// sink.write(7);
+/// assert_eq!(LAST.load(Ordering::Relaxed), 7);
+/// # }
+/// ```
+pub struct FnPtrWrite<T>(fn(T));
+
+impl<T> FnPtrWrite<T> {
+    /// Create a sink that calls `f` with every value written to it.
+    pub const fn new(f: fn(T)) -> Self {
+        FnPtrWrite(f)
+    }
+
+    /// Pass a value to the function.
+    pub fn write(&mut self, val: T) {
+        (self.0)(val);
+    }
+}
+
+impl<T> Clone for FnPtrWrite<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FnPtrWrite<T> {}
+
+impl<T> From<fn(T)> for FnPtrWrite<T> {
+    fn from(f: fn(T)) -> Self {
+        FnPtrWrite(f)
+    }
+}
+
+infallible_sink! {
+    [T] FnPtrWrite<T> => T;
+}
+
+/// A write-only buffer that combines every value written to it into a
+/// running result, which it writes to a single reference.
+///
+/// This is returned by [`reduce_into`](fn.reduce_into.html).
+pub struct ReduceInto<'a, T: 'a, F> {
+    out: WriteRef<'a, T>,
+    // Only `None` if `combine` panicked during a write.
+    acc: Option<T>,
+    combine: F,
+    len: usize,
+}
+
+impl<'a, T: Clone + 'a, F: FnMut(T, T) -> T> ReduceInto<'a, T, F> {
+    /// Combine a value with the running result, then write the new result
+    /// to the underlying reference.
+    ///
+    /// Every write contributes to the result, including repeated writes to
+    /// the same index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds, or if an earlier call to `combine`
+    /// panicked.
+    pub fn write(&mut self, idx: usize, val: T) {
+        check_index(idx, self.len);
+        let acc = self
+            .acc
+            .take()
+            .expect("ReduceInto used after `combine` panicked");
+        let acc = (self.combine)(acc, val);
+        self.out.write(acc.clone());
+        self.acc = Some(acc);
+    }
+
+    /// Returns the number of elements in this buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this buffer has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Create a write-only buffer of length `len` that reduces every value
+/// written to it with `combine`, starting from `init`, and writes the running
+/// result to `out`.
+///
+/// Values are combined in the order they're written, not in index order, so
+/// if `combine` isn't both associative and commutative, the result depends
+/// on the order of the writes. `out` isn't written to until the first write.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # fn main() {
+/// let mut total = 0i64;
+/// {
+///     let mut sum = write_ref::reduce_into(&mut total, 0, |acc, val| acc + val, 4);
+///     for i in 0..sum.len() {
+///         sum.write(i, i as i64 * 10);
+///     }
+/// }
+/// assert_eq!(total, 60);
+/// # }
+/// ```
+///
+/// ```should_panic
+/// # extern crate write_ref;
+/// # fn main() {
+/// let mut total = 0i64;
+/// write_ref::reduce_into(&mut total, 0, |acc, val| acc + val, 4).write(4, 1);
+/// # }
+/// ```
+pub fn reduce_into<'a, T: Clone + 'a, F: FnMut(T, T) -> T>(
+    out: impl Into<WriteRef<'a, T>>,
+    init: T,
+    combine: F,
+    len: usize,
+) -> ReduceInto<'a, T, F> {
+    ReduceInto {
+        out: out.into(),
+        acc: Some(init),
+        combine,
+        len,
+    }
+}
+
+/// A write-only view of a slice that converts values with a function before
+/// they are written.
+///
+/// This is returned by [`WriteSlice::contramap`](struct.WriteSlice.html#method.contramap).
+pub struct MapSlice<'a, T: 'a, F> {
+    slice: &'a mut [T],
+    f: F,
+}
+
+impl<'a, T: 'a, F> MapSlice<'a, T, F> {
+    /// Convert a value and write it to an element of the underlying slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn write<U>(&mut self, idx: usize, val: U)
+    where
+        F: FnMut(U) -> T,
+    {
+        self.slice[idx] = (self.f)(val);
+    }
+
+    /// Returns the number of elements in this view.
+    pub fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    /// Returns `true` if this view has a length of 0.
+    pub fn is_empty(&self) -> bool {
+        self.slice.is_empty()
+    }
+}
+
+/// Write every item of an iterator to a sink, in order, and return the
+/// number of items written.
+///
+/// What the sink ends up holding depends on the sink: a `&mut T` only keeps
+/// the last item, while a sink like [`SeqWrite`](struct.SeqWrite.html) keeps
+/// all of them.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::SeqWrite;
+/// # fn main() {
+/// let mut last = 0;
+/// assert_eq!(write_ref::drain(1..=3, &mut last), 3);
+/// assert_eq!(last, 3);
+///
+/// let mut all = [0; 3];
+/// assert_eq!(write_ref::drain(1..=3, SeqWrite::new(&mut all)), 3);
+/// assert_eq!(all, [1, 2, 3]);
+/// # }
+/// ```
+pub fn drain<T, I: IntoIterator<Item = T>>(iter: I, sink: impl IntoWriteRef<T>) -> usize {
+    let mut sink = sink.into_write_ref();
+    let mut count = 0;
+    for item in iter {
+        sink.write_dyn(item);
+        count += 1;
+    }
+    count
+}
+
+/// Consuming a stateful sink to recover the sink it wraps along with the
+/// state it collected.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::{CountingWrite, Finish};
+/// # fn main() {
+/// let mut a = 0;
+/// let mut counting = CountingWrite::new(&mut a);
+/// counting.write(1);
+/// counting.write(2);
+/// let (mut inner, count) = counting.finish();
+/// assert_eq!(count, 2);
+/// inner.write(3);
+/// assert_eq!(a, 3);
+/// # }
+/// ```
+pub trait Finish {
+    /// The sink this wraps.
+    type Inner;
+
+    /// The state collected by this sink.
+    type Report;
+
+    /// Returns the wrapped sink and the collected state.
+    fn finish(self) -> (Self::Inner, Self::Report);
+}
+
+/// Reports whether the reference was written to.
+impl<'a, T: 'a> Finish for WriteOnce<'a, T> {
+    type Inner = WriteRef<'a, T>;
+    type Report = bool;
+
+    fn finish(self) -> (WriteRef<'a, T>, bool) {
+        (WriteRef(self.dst), self.written)
+    }
+}
+
+/// Reports the number of writes.
+impl<W> Finish for CountingWrite<W> {
+    type Inner = W;
+    type Report = usize;
+
+    fn finish(self) -> (W, usize) {
+        (self.inner, self.count)
+    }
+}
+
+/// Reports the number of writes.
+impl<'a, T: 'a> Finish for CountingWriteSlice<'a, T> {
+    type Inner = WriteSlice<'a, T>;
+    type Report = usize;
+
+    fn finish(self) -> (WriteSlice<'a, T>, usize) {
+        (self.inner, self.count)
+    }
+}
+
+/// Reports the written values, in order. There is no wrapped sink.
+#[cfg(feature = "testing")]
+impl<T> Finish for ExpectWrites<T> {
+    type Inner = ();
+    type Report = Vec<T>;
+
+    fn finish(self) -> ((), Vec<T>) {
+        ((), self.0)
+    }
+}
+
+/// Reports the written indices and values, in order. There is no wrapped
+/// sink.
+#[cfg(feature = "testing")]
+impl<T> Finish for ExpectSliceWrites<T> {
+    type Inner = ();
+    type Report = Vec<(usize, T)>;
+
+    fn finish(self) -> ((), Vec<(usize, T)>) {
+        ((), self.written)
+    }
+}
+
+/// Reports the index of the first element that hasn't been written to, as
+/// returned by `first_unwritten`.
+#[cfg(feature = "alloc")]
+impl<W> Finish for Tracked<W> {
+    type Inner = W;
+    type Report = Option<usize>;
+
+    fn finish(self) -> (W, Option<usize>) {
+        let first = self.first_unwritten();
+        (self.inner, first)
+    }
+}
+
+/// Reports the number of bytes written.
+impl<'a> Finish for FmtSlice<'a> {
+    type Inner = WriteSlice<'a, u8>;
+    type Report = usize;
+
+    fn finish(self) -> (WriteSlice<'a, u8>, usize) {
+        (self.inner, self.pos)
+    }
+}
+
+/// Reports the number of values written.
+impl<'a, T: 'a> Finish for SeqWrite<'a, T> {
+    type Inner = WriteSlice<'a, T>;
+    type Report = usize;
+
+    fn finish(self) -> (WriteSlice<'a, T>, usize) {
+        (self.inner, self.pos)
+    }
+}
+
+/// Reports the number of values written.
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> Finish for GrowSink<'a, T> {
+    type Inner = &'a mut Vec<T>;
+    type Report = usize;
+
+    fn finish(self) -> (&'a mut Vec<T>, usize) {
+        (self.inner, self.pos)
+    }
+}
+
+/// A write-only view of a slice as a two-dimensional grid.
+///
+/// Element `(x, y)` is at index `y * row_stride + x` in the slice. The
+/// stride may be larger than the width, in which case the elements between
+/// the end of one row and the start of the next are padding that this view
+/// never writes to.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::Grid2D;
+/// # fn main() {
+/// // A 3x2 grid with one element of padding at the end of each row.
+/// let mut buf = [0; 8];
+/// {
+///     let mut grid = Grid2D::new(&mut buf, 3, 2, 4);
+///     grid.write_xy(0, 0, 1);
+///     grid.write_xy(2, 1, 2);
+/// }
+/// assert_eq!(buf, [1, 0, 0, 0, 0, 0, 2, 0]);
+/// # }
+/// ```
+pub struct Grid2D<'a, T: 'a> {
+    slice: &'a mut [T],
+    width: usize,
+    height: usize,
+    row_stride: usize,
+}
+
+impl<'a, T: 'a> Grid2D<'a, T> {
+    /// Create a grid of `width` by `height` elements over a slice, where
+    /// each row starts `row_stride` elements after the previous one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row_stride` is less than `width`, or if the slice is too
+    /// short to hold the grid.
+    pub fn new(
+        slice: impl Into<WriteSlice<'a, T>>,
+        width: usize,
+        height: usize,
+        row_stride: usize,
+    ) -> Self {
+        let slice = slice.into().0;
+        assert!(
+            row_stride >= width,
+            "row stride {} is less than the width {}",
+            row_stride,
+            width
+        );
+        if height > 0 {
+            let needed = (height - 1) * row_stride + width;
+            assert!(
+                slice.len() >= needed,
+                "a {}x{} grid with a row stride of {} needs {} elements but the len is {}",
+                width,
+                height,
+                row_stride,
+                needed,
+                slice.len()
+            );
+        }
+        Grid2D {
+            slice,
+            width,
+            height,
+            row_stride,
+        }
+    }
+
+    /// Write a value to the element at column `x` of row `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `y` is out of bounds.
+    pub fn write_xy(&mut self, x: usize, y: usize, val: T) {
+        assert!(
+            x < self.width && y < self.height,
+            "coordinates out of bounds: the size is {}x{} but the coordinates are ({}, {})",
+            self.width,
+            self.height,
+            x,
+            y
+        );
+        self.slice[y * self.row_stride + x] = val;
+    }
+
+    /// Write a value to every element in the columns `xs` of the rows `ys`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate write_ref;
+    /// # use write_ref::Grid2D;
+    /// # fn main() {
+    /// let mut buf = [0; 12];
+    /// Grid2D::new(&mut buf, 3, 3, 4).fill_rect(1..3, 0..2, 7);
+    /// assert_eq!(buf, [0, 7, 7, 0, 0, 7, 7, 0, 0, 0, 0, 0]);
+    /// # }
+    /// ```
+    pub fn fill_rect(&mut self, xs: Range<usize>, ys: Range<usize>, val: T)
+    where
+        T: Clone,
+    {
+        assert!(
+            xs.start <= xs.end
+                && xs.end <= self.width
+                && ys.start <= ys.end
+                && ys.end <= self.height,
+            "rectangle out of bounds: the size is {}x{} but the rectangle is {:?} by {:?}",
+            self.width,
+            self.height,
+            xs,
+            ys
+        );
+        for y in ys {
+            let start = y * self.row_stride;
+            self.slice[start + xs.start..start + xs.end].fill(val.clone());
+        }
+    }
+
+    /// Returns the number of columns in this grid.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows in this grid.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+}
+
+/// A write-only sink that keeps a copy of the last value written to it.
+///
+/// The copy can only be read by the owner of the wrapper. Sinks that are
+/// passed the wrapper as an `impl DynWriteRef<T>` still can't read anything.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::{LastWrite, SeqWrite, TryWriteRef, WriteRef};
+/// # fn main() {
+/// let mut a = 0;
+/// let mut sink = LastWrite::new(WriteRef::from(&mut a));
+/// assert_eq!(sink.last(), None);
+/// sink.write(1);
+/// sink.write(2);
+/// assert_eq!(sink.last(), Some(&2));
+///
+/// let mut buf = [0; 1];
+/// let mut sink = LastWrite::new(SeqWrite::new(&mut buf));
+/// assert_eq!(sink.try_write(1), Ok(()));
+/// assert_eq!(sink.try_write(2), Err(2));
+/// assert_eq!(sink.last(), Some(&1));
+/// # }
+/// ```
+pub struct LastWrite<W, T> {
+    inner: W,
+    last: Option<T>,
+}
+
+impl<W: DynWriteRef<T>, T: Clone> LastWrite<W, T> {
+    /// Wrap a sink so that the last value written to it is kept.
+    pub fn new(inner: W) -> Self {
+        LastWrite { inner, last: None }
+    }
+
+    /// Write a value to the underlying sink, keeping a copy of it.
+    pub fn write(&mut self, val: T) {
+        self.last = Some(val.clone());
+        self.inner.write_dyn(val);
+    }
+
+    /// Returns the last value written, or `None` if nothing has been.
+    pub fn last(&self) -> Option<&T> {
+        self.last.as_ref()
+    }
+}
+
+/// Reports the last value written.
+impl<W, T> Finish for LastWrite<W, T> {
+    type Inner = W;
+    type Report = Option<T>;
+
+    fn finish(self) -> (W, Option<T>) {
+        (self.inner, self.last)
+    }
+}
+
+impl<W: DynWriteRef<T>, T: Clone> DynWriteRef<T> for LastWrite<W, T> {
+    fn write_dyn(&mut self, value: T) {
+        self.write(value);
+    }
+}
+
+/// Fails if the underlying sink fails. Only values that were written
+/// successfully are kept.
+impl<W: TryWriteRef<T>, T: Clone> TryWriteRef<T> for LastWrite<W, T> {
+    type Error = W::Error;
+
+    fn try_write(&mut self, value: T) -> Result<(), W::Error> {
+        let copy = value.clone();
+        self.inner.try_write(value)?;
+        self.last = Some(copy);
+        Ok(())
+    }
+}
+
+impl<W: DynWriteRef<T>, T: Clone> IntoWriteRef<T> for LastWrite<W, T> {
+    type Target = Self;
+
+    fn into_write_ref(self) -> Self {
+        self
+    }
+}
+
+/// A fallible sink that retries failed writes to another fallible sink.
+///
+/// Each write is attempted up to a fixed number of times, calling a backoff
+/// hook between attempts. If every attempt fails, the error from the last
+/// one is returned. Retrying requires a copy of the value for every attempt
+/// but the last, so values must be `Clone`.
+///
+/// Its `Finish` implementation reports the total number of retries.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::{Finish, RetryWrite, TryWriteRef};
+/// // A sink that fails a number of times before accepting values.
+/// struct Flaky {
+///     failures: usize,
+///     value: u8,
+/// }
+/// impl TryWriteRef<u8> for Flaky {
+///     type Error = &'static str;
+///     fn try_write(&mut self, value: u8) -> Result<(), &'static str> {
+///         if self.failures > 0 {
+///             self.failures -= 1;
+///             return Err("busy");
+///         }
+///         self.value = value;
+///         Ok(())
+///     }
+/// }
+/// fn send<S: TryWriteRef<u8>>(sink: &mut S, value: u8) -> Result<(), S::Error> {
+///     sink.try_write(value)
+/// }
+/// # fn main() {
+/// let mut backoffs = 0;
+/// let mut sink = RetryWrite::new(Flaky { failures: 2, value: 0 }, 3)
+///     .with_backoff(|_| backoffs += 1);
+/// assert_eq!(send(&mut sink, 5), Ok(()));
+/// let (flaky, retries) = sink.finish();
+/// assert_eq!((flaky.value, retries, backoffs), (5, 2, 2));
+///
+/// let mut sink = RetryWrite::new(Flaky { failures: 3, value: 0 }, 3);
+/// assert_eq!(send(&mut sink, 5), Err("busy"));
+///
+/// // Retrying sinks can be nested, for 2 * 2 attempts in total.
+/// let mut sink = RetryWrite::new(RetryWrite::new(Flaky { failures: 3, value: 0 }, 2), 2);
+/// assert_eq!(send(&mut sink, 5), Ok(()));
+/// # }
+/// ```
+pub struct RetryWrite<W, F> {
+    inner: W,
+    attempts: usize,
+    backoff: F,
+    retries: usize,
+}
+
+impl<W> RetryWrite<W, fn(usize)> {
+    /// Wrap a sink so that each write is attempted up to `attempts` times,
+    /// without waiting between attempts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `attempts` is 0.
+    pub fn new(inner: W, attempts: usize) -> Self {
+        assert!(attempts != 0, "RetryWrite requires at least one attempt");
+        RetryWrite {
+            inner,
+            attempts,
+            backoff: |_| {},
+            retries: 0,
+        }
+    }
+}
+
+impl<W, F: FnMut(usize)> RetryWrite<W, F> {
+    /// Call `backoff` after each failed attempt that will be retried, with
+    /// the number of attempts that have failed so far.
+    pub fn with_backoff<G: FnMut(usize)>(self, backoff: G) -> RetryWrite<W, G> {
+        RetryWrite {
+            inner: self.inner,
+            attempts: self.attempts,
+            backoff,
+            retries: self.retries,
+        }
+    }
+}
+
+/// Attempts to write a value to the underlying sink until it succeeds or the
+/// attempts run out.
+impl<T: Clone, W: TryWriteRef<T>, F: FnMut(usize)> TryWriteRef<T> for RetryWrite<W, F> {
+    type Error = W::Error;
+
+    fn try_write(&mut self, value: T) -> Result<(), W::Error> {
+        for failed in 1..self.attempts {
+            if self.inner.try_write(value.clone()).is_ok() {
+                return Ok(());
+            }
+            self.retries += 1;
+            (self.backoff)(failed);
+        }
+        self.inner.try_write(value)
+    }
+}
+
+/// Reports the number of failed attempts that were retried.
+impl<W, F> Finish for RetryWrite<W, F> {
+    type Inner = W;
+    type Report = usize;
+
+    fn finish(self) -> (W, usize) {
+        (self.inner, self.retries)
+    }
+}
+
+/// A write-only sink that writes each value to one of two sinks, depending
+/// on a predicate.
+///
+/// This is returned by [`route`](fn.route.html).
+pub struct Route<F, A, B> {
+    pred: F,
+    if_true: A,
+    if_false: B,
+}
+
+impl<F, A, B> Route<F, A, B> {
+    /// Write a value to the first sink if the predicate returns `true` for
+    /// it, or to the second sink otherwise.
+    pub fn write<T>(&mut self, val: T)
+    where
+        F: FnMut(&T) -> bool,
+        A: DynWriteRef<T>,
+        B: DynWriteRef<T>,
+    {
+        if (self.pred)(&val) {
+            self.if_true.write_dyn(val);
+        } else {
+            self.if_false.write_dyn(val);
+        }
+    }
+}
+
+/// Create a write-only sink that writes each value to `if_true` if `pred`
+/// returns `true` for it, or to `if_false` otherwise.
+///
+/// Every value goes to exactly one of the sinks.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::{RouteError, SeqWrite, TryWriteRef};
+/// # fn main() {
+/// let mut evens = [0; 2];
+/// let mut odds = [0; 3];
+/// {
+///     let mut sink = write_ref::route(
+///         |v: &i32| v % 2 == 0,
+///         SeqWrite::new(&mut evens),
+///         SeqWrite::new(&mut odds),
+///     );
+///     for i in 1..=5 {
+///         sink.write(i);
+///     }
+///     assert_eq!(sink.try_write(6), Err(RouteError::IfTrue(6)));
+/// }
+/// assert_eq!(evens, [2, 4]);
+/// assert_eq!(odds, [1, 3, 5]);
+/// # }
+/// ```
+pub fn route<T, F: FnMut(&T) -> bool, A: IntoWriteRef<T>, B: IntoWriteRef<T>>(
+    pred: F,
+    if_true: A,
+    if_false: B,
+) -> Route<F, A::Target, B::Target> {
+    Route {
+        pred,
+        if_true: if_true.into_write_ref(),
+        if_false: if_false.into_write_ref(),
+    }
+}
+
+impl<T, F: FnMut(&T) -> bool, A: DynWriteRef<T>, B: DynWriteRef<T>> DynWriteRef<T>
+    for Route<F, A, B>
+{
+    fn write_dyn(&mut self, value: T) {
+        self.write(value);
+    }
+}
+
+/// Fails if the sink the value is routed to fails.
+impl<T, F: FnMut(&T) -> bool, A: TryWriteRef<T>, B: TryWriteRef<T>> TryWriteRef<T>
+    for Route<F, A, B>
+{
+    type Error = RouteError<A::Error, B::Error>;
+
+    fn try_write(&mut self, value: T) -> Result<(), RouteError<A::Error, B::Error>> {
+        if (self.pred)(&value) {
+            self.if_true.try_write(value).map_err(RouteError::IfTrue)
+        } else {
+            self.if_false.try_write(value).map_err(RouteError::IfFalse)
+        }
+    }
+}
+
+impl<T, F: FnMut(&T) -> bool, A: DynWriteRef<T>, B: DynWriteRef<T>> IntoWriteRef<T>
+    for Route<F, A, B>
+{
+    type Target = Self;
+
+    fn into_write_ref(self) -> Self {
+        self
+    }
+}
+
+/// The error returned by `Route::try_write`, saying which of the two sinks
+/// failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RouteError<A, B> {
+    /// The sink for values that match the predicate failed.
+    IfTrue(A),
+    /// The sink for values that don't match the predicate failed.
+    IfFalse(B),
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for RouteError<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RouteError::IfTrue(ref e) => write!(f, "sink for matching values failed: {}", e),
+            RouteError::IfFalse(ref e) => write!(f, "sink for other values failed: {}", e),
+        }
+    }
+}
+
+impl<A: Error, B: Error> Error for RouteError<A, B> {}
+
+/// A write-only reference that holds on to the value written to it until it
+/// is committed.
+///
+/// Only the last value written before `commit` reaches the underlying
+/// reference. If the `StagedWrite` is dropped without being committed, the
+/// staged value is dropped and the reference keeps its previous contents.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate write_ref;
+/// # use write_ref::StagedWrite;
+/// # fn main() {
+/// let mut a = 0;
+/// {
+///     let mut staged = StagedWrite::new(&mut a);
+///     staged.write(1);
+///     staged.write(2);
+///     staged.commit();
+/// }
+/// assert_eq!(a, 2);
+///
+/// {
+///     let mut staged = StagedWrite::new(&mut a);
+///     staged.write(3);
+/// }
+/// assert_eq!(a, 2);
+/// # }
+/// ```
+pub struct StagedWrite<'a, T: 'a> {
+    dst: &'a mut T,
+    staged: Option<T>,
+}
+
+impl<'a, T: 'a> StagedWrite<'a, T> {
+    /// Create a reference whose writes are staged until they're committed.
+    pub fn new(dst: &'a mut T) -> Self {
+        StagedWrite { dst, staged: None }
+    }
+
+    /// Stage a value, replacing any value staged before.
+    pub fn write(&mut self, val: T) {
+        self.staged = Some(val);
+    }
+
+    /// Write the staged value to the underlying reference. If nothing was
+    /// staged, the reference keeps its previous contents.
+    pub fn commit(self) {
+        if let Some(val) = self.staged {
+            *self.dst = val;
+        }
+    }
+}
+
+impl<'a, T: 'a> From<&'a mut T> for StagedWrite<'a, T> {
+    fn from(dst: &'a mut T) -> Self {
+        StagedWrite::new(dst)
+    }
+}
+
+infallible_sink! {
+    ['a, T: 'a] StagedWrite<'a, T> => T;
 }
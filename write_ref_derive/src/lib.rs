@@ -0,0 +1,164 @@
+#![deny(missing_docs)]
+
+//! Derive macro for `write_ref`.
+//!
+//! This crate shouldn't be used directly; enable the `derive` feature of
+//! `write_ref` instead.
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Spacing, TokenStream, TokenTree};
+
+/// Generates a `<Struct>Writers` type with a method for each field that
+/// returns a `WriteRef` to it, and a `writers` method on the struct that
+/// creates one.
+///
+/// Each method has the same visibility as its field, so private fields only
+/// get private methods. Only structs with named fields and no generic
+/// parameters are supported.
+#[proc_macro_derive(WriteRefFields)]
+pub fn derive_write_ref_fields(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(output) => output.parse().unwrap(),
+        Err(msg) => format!("compile_error!({:?});", msg).parse().unwrap(),
+    }
+}
+
+struct Field {
+    vis: String,
+    name: String,
+    ty: String,
+}
+
+fn expand(input: TokenStream) -> Result<String, &'static str> {
+    let mut tokens = input.into_iter().peekable();
+    let mut vis = String::new();
+    let name;
+    loop {
+        match tokens.next() {
+            // Skip attributes such as doc comments.
+            Some(TokenTree::Punct(ref p)) if p.as_char() == '#' => {
+                tokens.next();
+            }
+            Some(TokenTree::Ident(ref i)) if i.to_string() == "pub" => {
+                vis.push_str("pub");
+                if let Some(TokenTree::Group(g)) = tokens.peek().cloned() {
+                    if g.delimiter() == Delimiter::Parenthesis {
+                        vis.push_str(&g.to_string());
+                        tokens.next();
+                    }
+                }
+            }
+            Some(TokenTree::Ident(ref i)) if i.to_string() == "struct" => match tokens.next() {
+                Some(TokenTree::Ident(i)) => {
+                    name = i.to_string();
+                    break;
+                }
+                _ => return Err("expected a struct name"),
+            },
+            Some(_) => return Err("WriteRefFields can only be derived for structs"),
+            None => return Err("expected a struct"),
+        }
+    }
+
+    let body = match tokens.next() {
+        Some(TokenTree::Group(ref g)) if g.delimiter() == Delimiter::Brace => g.stream(),
+        Some(TokenTree::Punct(ref p)) if p.as_char() == '<' => {
+            return Err("WriteRefFields doesn't support generic structs")
+        }
+        _ => return Err("WriteRefFields requires a struct with named fields"),
+    };
+    let fields = parse_fields(body)?;
+
+    let writers = format!("{}Writers", name);
+    let mut methods = String::new();
+    for field in &fields {
+        methods.push_str(&format!(
+            "/// Returns a write-only reference to the `{name}` field.
+            {vis} fn {name}(&mut self) -> ::write_ref::WriteRef<'_, {ty}> {{
+                ::write_ref::WriteRef::from(&mut self.0.{name})
+            }}",
+            vis = field.vis,
+            name = field.name,
+            ty = field.ty,
+        ));
+    }
+    Ok(format!(
+        "/// Write-only references to the fields of a `{name}`.
+        {vis} struct {writers}<'a>(&'a mut {name});
+
+        impl<'a> {writers}<'a> {{
+            {methods}
+        }}
+
+        impl {name} {{
+            /// Returns write-only references to the fields of this struct.
+            {vis} fn writers(&mut self) -> {writers}<'_> {{
+                {writers}(self)
+            }}
+        }}",
+        vis = vis,
+        name = name,
+        writers = writers,
+        methods = methods,
+    ))
+}
+
+fn parse_fields(body: TokenStream) -> Result<Vec<Field>, &'static str> {
+    let mut fields = Vec::new();
+    let mut tokens = body.into_iter().peekable();
+    while tokens.peek().is_some() {
+        let mut vis = String::new();
+        let name = loop {
+            match tokens.next() {
+                Some(TokenTree::Punct(ref p)) if p.as_char() == '#' => {
+                    tokens.next();
+                }
+                Some(TokenTree::Ident(ref i)) if i.to_string() == "pub" => {
+                    vis.push_str("pub");
+                    if let Some(TokenTree::Group(g)) = tokens.peek().cloned() {
+                        if g.delimiter() == Delimiter::Parenthesis {
+                            vis.push_str(&g.to_string());
+                            tokens.next();
+                        }
+                    }
+                }
+                Some(TokenTree::Ident(i)) => break i.to_string(),
+                _ => return Err("expected a field name"),
+            }
+        };
+        match tokens.next() {
+            Some(TokenTree::Punct(ref p)) if p.as_char() == ':' => {}
+            _ => return Err("expected `:` after a field name"),
+        }
+
+        // Commas inside angle brackets, such as in `HashMap<K, V>`, don't end
+        // the type.
+        let mut ty = String::new();
+        let mut depth = 0usize;
+        let mut after_minus = false;
+        for token in tokens.by_ref() {
+            let mut joint = false;
+            if let TokenTree::Punct(ref p) = token {
+                match p.as_char() {
+                    ',' if depth == 0 => break,
+                    '<' => depth += 1,
+                    '>' if !after_minus => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+                joint = p.spacing() == Spacing::Joint;
+                after_minus = p.as_char() == '-' && joint;
+            } else {
+                after_minus = false;
+            }
+            ty.push_str(&token.to_string());
+            // Joint punctuation, such as the quote in a lifetime, must stay
+            // attached to the next token.
+            if !joint {
+                ty.push(' ');
+            }
+        }
+        fields.push(Field { vis, name, ty });
+    }
+    Ok(fields)
+}